@@ -1,4 +1,9 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, generator = InputGenerator, reference = reference);
+
+use advent_of_code::parse;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
+use std::str::FromStr;
 
 const TICKS_IN_DIAL: usize = 100;
 
@@ -14,46 +19,60 @@ struct Rotation {
     distance: usize,
 }
 
-impl Rotation {
-    fn from_string(input: &str) -> Self {
-        let mut chars = input.chars();
+impl FromStr for Rotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
 
-        let direction = match chars.next().unwrap() {
-            'L' => Direction::Left,
-            'R' => Direction::Right,
-            other => panic!("Expected L or R, got {other}"),
+        let direction = match chars.next() {
+            Some('L') => Direction::Left,
+            Some('R') => Direction::Right,
+            other => return Err(format!("expected L or R, got {other:?}")),
         };
 
-        let distance = chars.collect::<String>().parse::<usize>().unwrap();
+        let distance = chars.as_str().parse::<usize>().map_err(|e| e.to_string())?;
 
-        Self {
+        Ok(Self {
             direction,
             distance,
-        }
+        })
     }
 }
 
+fn rotations(input: &str) -> impl Iterator<Item = Rotation> + '_ {
+    parse::lines(input).map(|rotation| rotation.unwrap_or_else(|e| panic!("{e}")))
+}
+
 struct Dial<const TICKS: usize> {
     position: usize,
 }
 
 impl<const TICKS: usize> Dial<TICKS> {
-    fn step(&mut self, direction: Direction) {
+    /// Applies `rotation` and returns how many ticks pointed the dial at zero on the way,
+    /// including the tick it ends on.
+    fn rotate(&mut self, rotation: Rotation) -> usize {
+        let Rotation {
+            direction,
+            distance,
+        } = rotation;
+
         match direction {
-            Direction::Left => {
-                self.position = (self.position + TICKS - 1) % TICKS;
-            }
             Direction::Right => {
-                self.position = (self.position + 1) % TICKS;
+                let total = self.position + distance;
+                self.position = total % TICKS;
+                total / TICKS
+            }
+            Direction::Left => {
+                // NOTE: ticks to the left until the first zero, a full turn if we start on zero.
+                let to_zero = if self.position == 0 {
+                    TICKS
+                } else {
+                    self.position
+                };
+                self.position = (self.position + TICKS - distance % TICKS) % TICKS;
+                (distance + TICKS - to_zero) / TICKS
             }
-        }
-    }
-
-    fn rotate(&mut self, rotation: Rotation) {
-        let dist = rotation.distance % TICKS;
-
-        for _ in 0..dist {
-            self.step(rotation.direction);
         }
     }
 }
@@ -62,7 +81,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     let mut dial = Dial::<TICKS_IN_DIAL> { position: 50 };
     let mut count = 0;
 
-    for rotation in input.lines().map(Rotation::from_string) {
+    for rotation in rotations(input) {
         dial.rotate(rotation);
 
         if dial.position == 0 {
@@ -75,23 +94,74 @@ pub fn part_one(input: &str) -> Option<usize> {
 
 pub fn part_two(input: &str) -> Option<usize> {
     let mut dial = Dial::<TICKS_IN_DIAL> { position: 50 };
-    let mut count = 0;
+    let count = rotations(input).map(|rotation| dial.rotate(rotation)).sum();
+
+    Some(count)
+}
+
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = if rng.chance(1, 2) { 'L' } else { 'R' };
+                format!("{direction}{}", rng.below(1000))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Moves the dial one tick at a time.
+mod reference {
+    use super::{Direction, Rotation, TICKS_IN_DIAL, rotations};
+
+    pub fn rotate_by_ticks(position: usize, rotation: Rotation) -> (usize, usize) {
+        let mut position = position;
+        let mut zeros = 0;
 
-    for rotation in input.lines().map(Rotation::from_string) {
-        for _ in 0..(rotation.distance) {
-            dial.step(rotation.direction);
+        for _ in 0..rotation.distance {
+            position = match rotation.direction {
+                Direction::Left => (position + TICKS_IN_DIAL - 1) % TICKS_IN_DIAL,
+                Direction::Right => (position + 1) % TICKS_IN_DIAL,
+            };
 
-            if dial.position == 0 {
-                count += 1;
+            if position == 0 {
+                zeros += 1;
             }
         }
+
+        (position, zeros)
     }
 
-    Some(count)
+    /// How often the dial ends a rotation on zero, and how often it passes zero.
+    fn count_zeros(input: &str) -> (usize, usize) {
+        let mut position = 50;
+        let (mut ends, mut zeros) = (0, 0);
+
+        for rotation in rotations(input) {
+            let (next, hits) = rotate_by_ticks(position, rotation);
+            position = next;
+            ends += usize::from(position == 0);
+            zeros += hits;
+        }
+
+        (ends, zeros)
+    }
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        Some(count_zeros(input).0)
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        Some(count_zeros(input).1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::rotate_by_ticks;
     use super::*;
 
     #[test]
@@ -100,9 +170,52 @@ mod tests {
         assert_eq!(result, Some(3));
     }
 
+    #[test]
+    fn test_part_one_allocations() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = advent_of_code::assert_allocations!(part_one(&input), <= 0);
+        assert_eq!(result, Some(3));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6));
     }
+
+    #[test]
+    fn test_rotate_matches_ticks() {
+        let distances = [0, 1, 49, 50, 99, 100, 101, 150, 200, 250, 1000];
+
+        for position in 0..TICKS_IN_DIAL {
+            for direction in [Direction::Left, Direction::Right] {
+                for distance in distances {
+                    let rotation = Rotation {
+                        direction,
+                        distance,
+                    };
+                    let mut dial = Dial::<TICKS_IN_DIAL> { position };
+                    let zeros = dial.rotate(rotation);
+
+                    assert_eq!(
+                        (dial.position, zeros),
+                        rotate_by_ticks(position, rotation),
+                        "position {position}, distance {distance}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_inputs_match_ticks() {
+        let mut rng = Rng::new(1);
+
+        for size in 0..200 {
+            let input = InputGenerator::generate(&mut rng, size % 50);
+
+            assert_eq!(part_one(&input), reference::part_one(&input), "{input}");
+            assert_eq!(part_two(&input), reference::part_two(&input), "{input}");
+        }
+    }
 }
@@ -1,88 +1,195 @@
-advent_of_code::solution!(2);
-
-fn is_invalid_id(id: u64) -> bool {
-    let id_str = id.to_string();
-    let len = id_str.len();
+advent_of_code::solution!(
+    2,
+    input = Trimmed,
+    generator = InputGenerator,
+    reference = reference
+);
+
+use advent_of_code::checked::Checked;
+use advent_of_code::math;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
+use std::ops::RangeInclusive;
+
+fn parse_ranges(input: &str) -> impl Iterator<Item = RangeInclusive<u64>> {
+    input.split(',').map(|range| {
+        let (first, last) =
+            advent_of_code::scan!(range, "{}-{}", u64, u64).unwrap_or_else(|e| panic!("{e}"));
+        first..=last
+    })
+}
 
-    if len % 2 != 0 {
-        return false;
+/// Sum of the ids in `range` with `len` digits that are a block repeated `times` times.
+fn repetition_sum(range: &RangeInclusive<u64>, len: u32, times: u32) -> Checked<u128> {
+    let block = len / times;
+    let Some(multiplier) = math::repeat_multiplier(block, times) else {
+        return Checked(0);
+    };
+
+    // NOTE: blocks have at most 10 digits, so these can't overflow.
+    let smallest_block = math::pow10(block - 1).unwrap();
+    let largest_block = math::pow10(block).unwrap() - 1;
+
+    let first = range.start().div_ceil(multiplier).max(smallest_block);
+    let last = (range.end() / multiplier).min(largest_block);
+    if first > last {
+        return Checked(0);
     }
 
-    let first_half = &id_str[0..len / 2];
-    let second_half = &id_str[len / 2..];
-
-    first_half == second_half
+    // the ids are `multiplier * b` for every block `b` in `first..=last`.
+    let (first, last) = (u128::from(first), u128::from(last));
+    Checked((first + last) * (last - first + 1) / 2) * u128::from(multiplier)
 }
 
-fn is_invalid_id_2(id: u64) -> bool {
-    let id_str = id.to_string();
-    let len = id_str.len();
+/// Digit counts of the ids in `range`.
+fn lengths(range: &RangeInclusive<u64>) -> RangeInclusive<u32> {
+    math::digit_count(*range.start())..=math::digit_count(*range.end())
+}
 
-    for i in 1..=len / 2 {
-        if len % i != 0 {
-            continue;
-        }
+/// Sum of the ids in `range` that are a block repeated twice.
+fn doubled_sum(range: &RangeInclusive<u64>) -> Checked<u128> {
+    lengths(range)
+        .filter(|len| len.is_multiple_of(2))
+        .map(|len| repetition_sum(range, len, 2))
+        .sum()
+}
 
-        let first = &id_str[0..i];
-        let mut all_match = true;
-        for chunk in id_str.as_bytes().chunks(i) {
-            if chunk != first.as_bytes() {
-                all_match = false;
-                break;
+/// Sum of the ids in `range` that are a block repeated at least twice.
+///
+/// An id that repeats `t` times also repeats every divisor of `t` times, so the ids of the prime
+/// repetitions overlap. Inclusion-exclusion over the divisors of the length counts each id once,
+/// with the Möbius function as the sign, e.g. `222222` is added for `2` and `3` and removed for `6`.
+fn repeated_sum(range: &RangeInclusive<u64>) -> Checked<u128> {
+    let mut sum = Checked(0_i128);
+
+    for len in lengths(range) {
+        for times in (2..=len).filter(|&t| len.is_multiple_of(t)) {
+            let sign = -math::mobius(u64::from(times));
+            if sign != 0 {
+                let repetitions = i128::try_from(repetition_sum(range, len, times).get()).unwrap();
+                sum += Checked(i128::from(sign)) * repetitions;
             }
         }
-
-        if all_match {
-            return true;
-        }
     }
 
-    false
+    Checked(u128::try_from(sum.get()).unwrap())
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let res = input
-        .split(",")
-        .flat_map(|range| {
-            let (first, second) = range.split_once('-').unwrap();
-            let first = first.parse::<u64>().unwrap();
-            let second = second.parse::<u64>().unwrap();
+pub fn part_one(input: &str) -> Option<u128> {
+    let res: Checked<u128> = parse_ranges(input).map(|range| doubled_sum(&range)).sum();
+    Some(res.get())
+}
 
-            (first..=second).filter(|id| is_invalid_id(*id))
-        })
-        .sum();
+pub fn part_two(input: &str) -> Option<u128> {
+    let res: Checked<u128> = parse_ranges(input).map(|range| repeated_sum(&range)).sum();
+    Some(res.get())
+}
 
-    Some(res)
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|_| {
+                // NOTE: ranges span up to ten digits, so they cross several id lengths.
+                let digits = u32::try_from(rng.range(1..=18)).unwrap();
+                let width = u32::try_from(rng.range(1..=10)).unwrap();
+                let start = rng.below(10_u64.pow(digits));
+                format!("{start}-{}", start + rng.below(10_u64.pow(width)))
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let res = input
-        .split(",")
-        .flat_map(|range| {
-            let (first, second) = range.split_once('-').unwrap();
-            let first = first.parse::<u64>().unwrap();
-            let second = second.parse::<u64>().unwrap();
+/// Enumerates the ids of every period and removes the ids that repeat at several periods.
+mod reference {
+    use super::{RangeInclusive, math, parse_ranges};
+
+    /// Ids in `range` whose digits are a block repeated `times` times, for every `times` that `repeats` accepts.
+    pub fn repeated_ids(range: &RangeInclusive<u64>, repeats: impl Fn(u32) -> bool) -> Vec<u64> {
+        let (start, end) = (*range.start(), *range.end());
+        let mut ids = vec![];
+
+        for len in math::digit_count(start)..=math::digit_count(end) {
+            for times in (2..=len).filter(|&t| len.is_multiple_of(t) && repeats(t)) {
+                let block = len / times;
+                let Some(multiplier) = math::repeat_multiplier(block, times) else {
+                    continue;
+                };
+
+                let smallest_block = math::pow10(block - 1).unwrap();
+                let largest_block = math::pow10(block).unwrap() - 1;
+
+                let first = start.div_ceil(multiplier).max(smallest_block);
+                let last = (end / multiplier).min(largest_block);
+
+                ids.extend((first..=last).map(|b| b * multiplier));
+            }
+        }
+
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    fn sum(input: &str, repeats: impl Fn(u32) -> bool + Copy) -> Option<u128> {
+        Some(
+            parse_ranges(input)
+                .flat_map(|range| repeated_ids(&range, repeats))
+                .map(u128::from)
+                .sum(),
+        )
+    }
 
-            (first..=second).filter(|id| is_invalid_id_2(*id))
-        })
-        .sum();
+    pub fn part_one(input: &str) -> Option<u128> {
+        sum(input, |times| times == 2)
+    }
 
-    Some(res)
+    pub fn part_two(input: &str) -> Option<u128> {
+        sum(input, |_| true)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::repeated_ids;
     use super::*;
 
+    // NOTE: compares the digits as text, so the closed form isn't checked against its own arithmetic.
+    fn is_repetition(id: u64, times: usize) -> bool {
+        let digits = id.to_string();
+        if !digits.len().is_multiple_of(times) {
+            return false;
+        }
+
+        let (block, rest) = digits.split_at(digits.len() / times);
+        rest.as_bytes()
+            .chunks(block.len())
+            .all(|chunk| chunk == block.as_bytes())
+    }
+
+    fn is_invalid_id(id: u64) -> bool {
+        is_repetition(id, 2)
+    }
+
+    fn is_invalid_id_2(id: u64) -> bool {
+        (2..=id.to_string().len()).any(|times| is_repetition(id, times))
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_normalized(
+            "examples", DAY, INPUT_MODE,
+        ));
         assert_eq!(result, Some(1227775554));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file_normalized(
+            "examples", DAY, INPUT_MODE,
+        ));
         assert_eq!(result, Some(4174379265));
     }
 
@@ -102,4 +209,40 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_sums_match_brute_force() {
+        let mut rng = Rng::new(2);
+        let mut ranges = vec![0..=0, 1..=100_000, 95..=115, 999_990..=1_000_010];
+
+        for _ in 0..200 {
+            let digits = u32::try_from(rng.range(1..=9)).unwrap();
+            let start = rng.below(10_u64.pow(digits));
+            ranges.push(start..=start + rng.below(5_000));
+        }
+
+        for range in ranges {
+            let part_one: u64 = range.clone().filter(|id| is_invalid_id(*id)).sum();
+            assert_eq!(doubled_sum(&range).get(), part_one.into(), "{range:?}");
+
+            let part_two: u64 = range.clone().filter(|id| is_invalid_id_2(*id)).sum();
+            assert_eq!(repeated_sum(&range).get(), part_two.into(), "{range:?}");
+        }
+    }
+
+    #[test]
+    fn test_wide_ranges() {
+        let range = 1..=999_999_999_999;
+        let ids = repeated_ids(&range, |_| true);
+        let expected: u128 = ids.iter().copied().map(u128::from).sum();
+        assert_eq!(repeated_sum(&range).get(), expected);
+
+        // every block of `b` digits, repeated twice.
+        let doubled = |b: u32| {
+            let (first, last) = (10_u128.pow(b - 1), 10_u128.pow(b) - 1);
+            (first + last) * (last - first + 1) / 2 * (10_u128.pow(b) + 1)
+        };
+        let expected = (1..=9).map(doubled).sum();
+        assert_eq!(part_one("1-999999999999999999"), Some(expected));
+    }
 }
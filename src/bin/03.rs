@@ -1,63 +1,163 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, generator = InputGenerator, reference = reference);
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let res = input
-        .lines()
-        .map(|line| {
-            let digits = line.bytes().map(|b| b - b'0').collect::<Vec<u8>>();
-            let mut max: u64 = 0;
-
-            for i in 0..digits.len() {
-                for j in i + 1..digits.len() {
-                    let number = 10 * digits[i] + digits[j];
-                    max = std::cmp::max(number as u64, max);
-                }
-            }
+use advent_of_code::math;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
+
+/// The largest number that can be formed from `k` of the `digits`, keeping their order.
+/// There must be at least `k` digits. Returns [`None`] if the number does not fit into a `u128`.
+fn max_subsequence_number(digits: &[u8], k: usize) -> Option<u128> {
+    debug_assert!(
+        k <= digits.len(),
+        "can't pick {k} of {} digits",
+        digits.len()
+    );
+
+    // NOTE: a stack of decreasing digits. a smaller digit is dropped when a larger one follows,
+    // as long as enough digits remain to still pick `k`.
+    let mut stack: Vec<u8> = Vec::with_capacity(k);
+
+    for (i, &digit) in digits.iter().enumerate() {
+        let remaining = digits.len() - i;
+        while stack
+            .last()
+            .is_some_and(|&top| top < digit && stack.len() - 1 + remaining >= k)
+        {
+            stack.pop();
+        }
 
-            max
+        if stack.len() < k {
+            stack.push(digit);
+        }
+    }
+
+    stack.iter().try_fold(0_u128, |n, &d| {
+        n.checked_mul(10)?.checked_add(u128::from(d))
+    })
+}
+
+/// Sum of the largest `k` digit numbers of all banks, or [`None`] if it overflows.
+///
+/// # Panics
+/// Panics if a bank has fewer than `k` digits.
+fn total_joltage(input: &str, k: usize) -> Option<u64> {
+    let total = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let digits: Vec<u8> = line.bytes().filter_map(math::digit).collect();
+            assert!(
+                digits.len() >= k,
+                "line {} has {} digits, can't pick {k}",
+                i + 1,
+                digits.len()
+            );
+            max_subsequence_number(&digits, k)
         })
-        .sum();
+        .sum::<Option<u128>>()?;
+
+    u64::try_from(total).ok()
+}
 
-    Some(res)
+pub fn part_one(input: &str) -> Option<u64> {
+    total_joltage(input, 2)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let res = input
-        .lines()
-        .map(|line| {
-            let digits: Vec<u8> = line.bytes().map(|b| b - b'0').collect();
-            let n = digits.len();
-            let k = 12;
-
-            let mut result: u64 = 0;
-            let mut start = 0;
-
-            for i in 0..k {
-                let remaining_to_pick = k - i - 1;
-                let end = n - remaining_to_pick;
-
-                let mut max_idx = start;
-                for j in start + 1..end {
-                    if digits[j] > digits[max_idx] {
-                        max_idx = j;
-                    }
-                }
+    total_joltage(input, 12)
+}
+
+struct InputGenerator;
 
-                result = result * 10 + digits[max_idx] as u64;
-                start = max_idx + 1;
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let len = 12 + rng.index(89);
+                (0..len)
+                    .map(|_| char::from(b'1' + u8::try_from(rng.below(9)).unwrap()))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+mod reference {
+    use super::math;
+
+    /// Tries every pair of digits.
+    pub fn max_pair_quadratic(digits: &[u8]) -> u64 {
+        let mut max = 0;
+
+        for i in 0..digits.len() {
+            for j in i + 1..digits.len() {
+                max = max.max(u64::from(10 * digits[i] + digits[j]));
             }
+        }
 
-            result
-        })
-        .sum();
+        max
+    }
 
-    Some(res)
+    /// Searches the window of each picked digit for its maximum.
+    pub fn max_subsequence_greedy(digits: &[u8], k: usize) -> u128 {
+        let mut result = 0;
+        let mut start = 0;
+
+        for i in 0..k {
+            let end = digits.len() - (k - i - 1);
+
+            let mut max_idx = start;
+            for j in start + 1..end {
+                if digits[j] > digits[max_idx] {
+                    max_idx = j;
+                }
+            }
+
+            result = result * 10 + u128::from(digits[max_idx]);
+            start = max_idx + 1;
+        }
+
+        result
+    }
+
+    fn digits(line: &str) -> Vec<u8> {
+        line.bytes().filter_map(math::digit).collect()
+    }
+
+    pub fn part_one(input: &str) -> Option<u64> {
+        input
+            .lines()
+            .map(|line| {
+                let digits = digits(line);
+                (digits.len() >= 2).then(|| max_pair_quadratic(&digits))
+            })
+            .sum()
+    }
+
+    pub fn part_two(input: &str) -> Option<u64> {
+        input
+            .lines()
+            .map(|line| {
+                let digits = digits(line);
+                (digits.len() >= 12).then(|| max_subsequence_greedy(&digits, 12))
+            })
+            .sum::<Option<u128>>()
+            .and_then(|total| u64::try_from(total).ok())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::{max_pair_quadratic, max_subsequence_greedy};
     use super::*;
 
+    fn random_digits(rng: &mut Rng, len: u64) -> Vec<u8> {
+        (0..len)
+            .map(|_| u8::try_from(rng.below(10)).unwrap())
+            .collect()
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -69,4 +169,44 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(3121910778619));
     }
+
+    #[test]
+    fn test_matches_quadratic_search() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..500 {
+            let len = 2 + rng.below(40);
+            let digits = random_digits(&mut rng, len);
+            assert_eq!(
+                max_subsequence_number(&digits, 2),
+                Some(u128::from(max_pair_quadratic(&digits))),
+                "{digits:?}"
+            );
+
+            // NOTE: at most 38 digits, so the reference can't overflow.
+            let k = usize::try_from(1 + rng.below(len.min(38))).unwrap();
+            assert_eq!(
+                max_subsequence_number(&digits, k),
+                Some(max_subsequence_greedy(&digits, k)),
+                "{digits:?}, k = {k}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detects_overflow() {
+        let nines = [9; 40];
+        assert_eq!(
+            max_subsequence_number(&nines, 38),
+            Some(10_u128.pow(38) - 1)
+        );
+        assert_eq!(max_subsequence_number(&nines, 39), None);
+        assert_eq!(total_joltage(&"9".repeat(20), 20), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 has 3 digits, can't pick 4")]
+    fn test_rejects_short_banks() {
+        total_joltage("12345\n123", 4);
+    }
 }
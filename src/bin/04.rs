@@ -1,15 +1,14 @@
-advent_of_code::solution!(4);
-
-const DIRECTIONS: [(isize, isize); 8] = [
-    (-1, 0),
-    (1, 0),
-    (0, -1),
-    (0, 1),
-    (-1, -1),
-    (-1, 1),
-    (1, -1),
-    (1, 1),
-];
+advent_of_code::solution!(
+    4,
+    parse = parse_grid,
+    generator = InputGenerator,
+    reference = reference,
+    report = rounds_report
+);
+
+use advent_of_code::grid::Grid;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
 
 #[derive(PartialEq, Clone, Copy)]
 enum Cell {
@@ -29,106 +28,156 @@ impl TryFrom<char> for Cell {
     }
 }
 
-struct Grid {
-    cells: Vec<Vec<Cell>>,
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Cell::Empty => '.',
+            Cell::Roll => '@',
+        };
+        write!(f, "{c}")
+    }
 }
 
-impl Grid {
-    fn from_input(input: &str) -> Self {
-        let cells: Vec<Vec<Cell>> = input
-            .lines()
-            .map(|line| {
-                line.chars()
-                    .map(|c| Cell::try_from(c).expect("valid cell character"))
-                    .collect()
-            })
-            .collect();
-
-        Self { cells }
-    }
+fn parse_grid(input: &str) -> Grid<Cell> {
+    Grid::parse(input).expect("valid cell characters")
+}
 
-    fn get(&self, row: usize, col: usize) -> Option<&Cell> {
-        self.cells.get(row).and_then(|r| r.get(col))
-    }
+fn can_forklift_access(grid: &Grid<Cell>, row: usize, col: usize) -> bool {
+    let adjacent_rolls = grid
+        .neighbors8(row, col)
+        .filter(|(_, _, cell)| **cell == Cell::Roll)
+        .count();
 
-    fn neighbor(&self, row: usize, col: usize, dr: isize, dc: isize) -> Option<&Cell> {
-        let nrow = row.checked_add_signed(dr)?;
-        let ncol = col.checked_add_signed(dc)?;
-        self.get(nrow, ncol)
-    }
+    adjacent_rolls < 4
+}
 
-    fn can_forklift_access(&self, row: usize, col: usize) -> bool {
-        let adjacent_rolls = DIRECTIONS
-            .iter()
-            .filter(|&&(dr, dc)| self.neighbor(row, col, dr, dc) == Some(&Cell::Roll))
-            .count();
+fn accessible_rolls(grid: &Grid<Cell>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    grid.iter().filter_map(|(row, col, cell)| {
+        (*cell == Cell::Roll && can_forklift_access(grid, row, col)).then_some((row, col))
+    })
+}
 
-        adjacent_rolls < 4
+/// Number of rolls removed in each round, where a round removes every roll that is accessible when it starts.
+///
+/// Counts the adjacent rolls of every roll once and decrements them as rolls are removed,
+/// so each roll is only revisited when one of its neighbors disappears.
+fn removal_rounds(grid: &Grid<Cell>) -> Vec<usize> {
+    let mut adjacent = grid.map(|_| 0_usize);
+    for (row, col, cell) in grid.iter() {
+        if *cell == Cell::Roll {
+            adjacent[(row, col)] = grid
+                .neighbors8(row, col)
+                .filter(|(_, _, cell)| **cell == Cell::Roll)
+                .count();
+        }
     }
 
-    fn remove_roll(&mut self, row: usize, col: usize) {
-        self.cells[row][col] = Cell::Empty;
+    // empty cells and rolls that are already scheduled for removal are never revisited.
+    let mut scheduled = grid.map(|cell| *cell == Cell::Empty);
+    let mut round: Vec<(usize, usize)> = accessible_rolls(grid).collect();
+    for &position in &round {
+        scheduled[position] = true;
     }
 
-    fn iter(&self) -> impl Iterator<Item = (usize, usize, &Cell)> {
-        self.cells.iter().enumerate().flat_map(|(row, cells)| {
-            cells
-                .iter()
-                .enumerate()
-                .map(move |(col, cell)| (row, col, cell))
-        })
-    }
+    let mut rounds = vec![];
 
-    fn accessible_rolls(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
-        self.iter().filter_map(|(row, col, cell)| {
-            (*cell == Cell::Roll && self.can_forklift_access(row, col)).then_some((row, col))
-        })
-    }
-}
+    while !round.is_empty() {
+        rounds.push(round.len());
+        let mut next = vec![];
 
-impl std::fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.cells {
-            for cell in row {
-                let c = match cell {
-                    Cell::Empty => '.',
-                    Cell::Roll => '@',
-                };
-                write!(f, "{c}")?;
+        for (row, col) in round {
+            for (r, c, _) in grid.neighbors8(row, col) {
+                if scheduled[(r, c)] {
+                    continue;
+                }
+
+                adjacent[(r, c)] -= 1;
+                if adjacent[(r, c)] < 4 {
+                    scheduled[(r, c)] = true;
+                    next.push((r, c));
+                }
             }
-            writeln!(f)?;
         }
-        Ok(())
+
+        round = next;
     }
+
+    rounds
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let grid = Grid::from_input(input);
-    let count = grid.accessible_rolls().count() as u64;
+    let grid = parse_grid(input);
+    let count = accessible_rolls(&grid).count() as u64;
     Some(count)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let mut grid = Grid::from_input(input);
-    let mut total_count: u64 = 0;
+    let grid = parse_grid(input);
+    let total_count = removal_rounds(&grid).iter().sum::<usize>() as u64;
+    Some(total_count)
+}
 
-    loop {
-        let to_remove: Vec<_> = grid.accessible_rolls().collect();
-        if to_remove.is_empty() {
-            break;
-        }
+/// One line per removal round, to see how the removals progress.
+/// Run with `cargo run --release --bin 04 -- --report` to print it for the input.
+fn rounds_report(input: &str) -> String {
+    removal_rounds(&parse_grid(input))
+        .iter()
+        .enumerate()
+        .map(|(i, count)| format!("Round {:>3}: {count:>5} removed\n", i + 1))
+        .collect()
+}
+
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let (width, height) = (1 + rng.index(size + 1), 1 + rng.index(size + 1));
 
-        total_count += to_remove.len() as u64;
-        for (row, col) in to_remove {
-            grid.remove_roll(row, col);
+        (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.chance(2, 3) { '@' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Rescans the whole grid in every round.
+mod reference {
+    use super::{Cell, Grid, accessible_rolls, parse_grid};
+
+    pub fn removal_rounds_by_rescanning(mut grid: Grid<Cell>) -> Vec<usize> {
+        let mut rounds = vec![];
+
+        loop {
+            let to_remove: Vec<_> = accessible_rolls(&grid).collect();
+            if to_remove.is_empty() {
+                return rounds;
+            }
+
+            rounds.push(to_remove.len());
+            for position in to_remove {
+                grid[position] = Cell::Empty;
+            }
         }
     }
 
-    Some(total_count)
+    pub fn part_one(input: &str) -> Option<u64> {
+        let rounds = removal_rounds_by_rescanning(parse_grid(input));
+        Some(rounds.first().copied().unwrap_or(0) as u64)
+    }
+
+    pub fn part_two(input: &str) -> Option<u64> {
+        let rounds = removal_rounds_by_rescanning(parse_grid(input));
+        Some(rounds.iter().sum::<usize>() as u64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::removal_rounds_by_rescanning;
     use super::*;
 
     #[test]
@@ -142,4 +191,42 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(43));
     }
+
+    #[test]
+    fn test_removal_rounds() {
+        let grid = parse_grid(&advent_of_code::template::read_file("examples", DAY));
+        assert_eq!(removal_rounds(&grid), removal_rounds_by_rescanning(grid));
+    }
+
+    #[test]
+    fn test_rounds_report() {
+        let report = rounds_report(&advent_of_code::template::read_file("examples", DAY));
+        assert!(report.starts_with("Round   1:    13 removed\n"));
+    }
+
+    #[test]
+    fn test_random_grids_match_rescanning() {
+        let mut rng = Rng::new(4);
+
+        for _ in 0..100 {
+            let width = 1 + rng.index(30);
+            let height = 1 + rng.index(30);
+            let cells = (0..width * height)
+                .map(|_| {
+                    if rng.chance(2, 3) {
+                        Cell::Roll
+                    } else {
+                        Cell::Empty
+                    }
+                })
+                .collect();
+            let grid = Grid::from_vec(width, cells).unwrap();
+
+            assert_eq!(
+                removal_rounds(&grid),
+                removal_rounds_by_rescanning(grid.clone()),
+                "{grid}"
+            );
+        }
+    }
 }
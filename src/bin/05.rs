@@ -1,57 +1,108 @@
-use std::ops::RangeInclusive;
-
-advent_of_code::solution!(5);
-
-fn ranges_from_input(input: &str) -> Vec<RangeInclusive<u64>> {
-    input
-        .lines()
-        .map(|line| {
-            let (from, to) = line.split_once("-").unwrap();
-            let from = from.parse::<u64>().unwrap();
-            let to = to.parse::<u64>().unwrap();
-            from..=to
-        })
+use advent_of_code::interval_set::IntervalSet;
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
+
+advent_of_code::solution!(
+    5,
+    input = Normalized,
+    generator = InputGenerator,
+    reference = reference
+);
+
+fn ranges_from_input(input: &str) -> Result<IntervalSet<u64>, ParseError> {
+    parse::lines_with(input, |line| advent_of_code::scan!(line, "{}-{}", u64, u64))
+        .map(|range| range.map(|(from, to)| from..=to))
         .collect()
 }
 
+fn split_input(input: &str) -> (&str, &str) {
+    let mut sections = parse::sections(input);
+    let ranges = sections.next().unwrap_or_default();
+    let ingredients = sections.next().unwrap_or_default();
+    (ranges, ingredients)
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
-    let (ranges_str, ingredients_str) = input.split_once("\n\n").unwrap();
+    let (ranges, ingredients) = split_input(input);
 
-    let ranges = ranges_from_input(ranges_str);
+    let ranges = ranges_from_input(ranges).unwrap_or_else(|e| panic!("{e}"));
 
-    let result = ingredients_str
-        .lines()
-        .map(|ingredient| ingredient.parse::<u64>().unwrap())
-        .filter(|i| ranges.iter().any(|range| range.contains(i)))
+    let result = parse::lines::<u64>(ingredients)
+        .map(|ingredient| ingredient.unwrap_or_else(|e| panic!("{e}")))
+        .filter(|i| ranges.contains(*i))
         .count();
 
     Some(result)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (ranges, _) = input.split_once("\n\n").unwrap();
+    let (ranges, _) = split_input(input);
+
+    let ranges = ranges_from_input(ranges).unwrap_or_else(|e| panic!("{e}"));
+
+    usize::try_from(ranges.len()).ok()
+}
+
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        // NOTE: a random magnitude per input, so that small inputs still have overlapping ranges.
+        let scale = 10_u64.pow(u32::try_from(rng.range(1..=14)).unwrap());
+
+        let ranges = (0..=size / 2).map(|_| {
+            let from = rng.below(scale);
+            format!("{from}-{}", from + rng.below(scale / 4 + 1))
+        });
+        let ranges: Vec<String> = ranges.collect();
+        let ingredients: Vec<String> = (0..size).map(|_| rng.below(scale).to_string()).collect();
+
+        format!("{}\n\n{}\n", ranges.join("\n"), ingredients.join("\n"))
+    }
+}
+
+/// Checks every ingredient against every range, and merges sorted ranges without an [`IntervalSet`].
+mod reference {
+    use super::{parse, split_input};
 
-    let mut ranges = ranges_from_input(ranges);
+    fn ranges(input: &str) -> Vec<(u64, u64)> {
+        parse::lines_with(split_input(input).0, |line| {
+            advent_of_code::scan!(line, "{}-{}", u64, u64)
+        })
+        .map(|range| range.unwrap_or_else(|e| panic!("{e}")))
+        .collect()
+    }
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        let ranges = ranges(input);
 
-    ranges.sort_by_key(|range| *range.start());
+        let result = parse::lines::<u64>(split_input(input).1)
+            .map(|ingredient| ingredient.unwrap_or_else(|e| panic!("{e}")))
+            .filter(|&i| ranges.iter().any(|&(from, to)| from <= i && i <= to))
+            .count();
 
-    let mut merged: Vec<RangeInclusive<u64>> = Vec::new();
+        Some(result)
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        let mut ranges = ranges(input);
+        ranges.retain(|(from, to)| from <= to);
+        ranges.sort_unstable();
 
-    for range in ranges {
-        if let Some(last) = merged.last_mut() {
-            if last.end() >= range.start() {
-                let max_end = std::cmp::max(last.end(), range.end());
-                *last = *last.start()..=*max_end;
-            } else {
-                merged.push(range);
+        let mut total = 0;
+        let mut covered_to = None;
+
+        for (from, to) in ranges {
+            let from = covered_to.map_or(from, |end: u64| from.max(end + 1));
+            if from <= to {
+                total += to - from + 1;
+                covered_to = Some(to);
             }
-        } else {
-            merged.push(range);
         }
-    }
 
-    let res = merged.into_iter().map(|range| range.count()).sum();
-    Some(res)
+        usize::try_from(total).ok()
+    }
 }
 
 #[cfg(test)]
@@ -60,13 +111,29 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_normalized(
+            "examples", DAY, INPUT_MODE,
+        ));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file_normalized(
+            "examples", DAY, INPUT_MODE,
+        ));
         assert_eq!(result, Some(14));
     }
+
+    #[test]
+    fn test_random_inputs_match_reference() {
+        let mut rng = Rng::new(5);
+
+        for size in 0..100 {
+            let input = InputGenerator::generate(&mut rng, size);
+
+            assert_eq!(part_one(&input), reference::part_one(&input), "{input}");
+            assert_eq!(part_two(&input), reference::part_two(&input), "{input}");
+        }
+    }
 }
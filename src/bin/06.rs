@@ -1,4 +1,8 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, generator = InputGenerator, reference = reference);
+
+use advent_of_code::fixed_width::{Block, CharMatrix};
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
 
 #[derive(Clone, Copy)]
 enum Op {
@@ -17,69 +21,166 @@ impl TryFrom<&str> for Op {
     }
 }
 
-struct Problem {
+/// A problem of the worksheet, with its operands still in their aligned columns.
+struct Problem<'a> {
     op: Op,
-    inputs: Vec<u64>,
+    operands: Block<'a>,
 }
 
-impl Problem {
-    fn solve(&self) -> u64 {
+impl Problem<'_> {
+    fn apply(&self, inputs: impl Iterator<Item = String>) -> u64 {
+        let inputs = inputs
+            .map(|input| input.trim().to_string())
+            .filter(|input| !input.is_empty())
+            .map(|input| {
+                input
+                    .parse::<u64>()
+                    .unwrap_or_else(|e| panic!("invalid operand `{input}`: {e}"))
+            });
+
         match self.op {
-            Op::Plus => self.inputs.iter().sum(),
-            Op::Multiply => self.inputs.iter().product(),
+            Op::Plus => inputs.sum(),
+            Op::Multiply => inputs.product(),
         }
     }
 
-    fn solve_reverse(&self) -> u64 {}
-}
-
-fn parse_input(input: &str) -> Vec<Problem> {
-    let operators = input
-        .lines()
-        .rev()
-        .next()
-        .unwrap()
-        .split_whitespace()
-        .filter_map(|symbol| Op::try_from(symbol).ok())
-        .collect::<Vec<Op>>();
-
-    let problem_lines = input
-        .lines()
-        .rev()
-        .skip(1)
-        .map(|line| {
-            line.split_whitespace()
-                .filter_map(|i| i.parse::<u64>().ok())
-                .collect::<Vec<u64>>()
-        })
-        .collect::<Vec<Vec<u64>>>();
-
-    let mut problems = vec![];
+    /// Reads one operand per row.
+    fn solve(&self) -> u64 {
+        self.apply(self.operands.rows())
+    }
 
-    for i in 0..problem_lines[0].len() {
-        let mut inputs = vec![];
-        for j in 0..problem_lines.len() {
-            inputs.push(problem_lines[j][i]);
-        }
-        let problem = Problem {
-            inputs,
-            op: operators[i],
-        };
-        problems.push(problem);
+    /// Reads one operand per column, from right to left, with the most significant digit at the top.
+    fn solve_reverse(&self) -> u64 {
+        // NOTE: shorter operands leave gaps within a column, e.g. `12`, ` 3` and `45` aligned right.
+        let columns = self.operands.columns_rtl();
+        self.apply(columns.map(|column| column.replace(' ', "")))
     }
+}
 
-    problems
+fn parse_input(worksheet: &CharMatrix) -> Vec<Problem<'_>> {
+    worksheet
+        .blocks()
+        .map(|block| {
+            let (operands, operator) = block.split_last_row().unwrap();
+            let op = Op::try_from(operator.row(0).trim()).unwrap();
+            Problem { op, operands }
+        })
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let problems = parse_input(input);
-    let res = problems.iter().map(|p| p.solve()).sum();
+    let worksheet = CharMatrix::new(input);
+    let res = parse_input(&worksheet).iter().map(Problem::solve).sum();
 
     Some(res)
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let problems = parse_input(input);
+    let worksheet = CharMatrix::new(input);
+    let res = parse_input(&worksheet)
+        .iter()
+        .map(Problem::solve_reverse)
+        .sum();
+
+    Some(res)
+}
+
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let operand_rows = 2 + rng.index(3);
+        let mut rows = vec![String::new(); operand_rows + 1];
+
+        for problem in 0..size.max(1) {
+            if problem > 0 {
+                rows.iter_mut().for_each(|row| row.push(' '));
+            }
+
+            let width = 1 + rng.index(4);
+            // NOTE: at least one operand spans the whole problem, so no column of it is blank.
+            let full = rng.index(operand_rows);
+            let left_aligned = rng.chance(1, 2);
+
+            for (i, row) in rows[..operand_rows].iter_mut().enumerate() {
+                let len = if i == full {
+                    width
+                } else {
+                    1 + rng.index(width)
+                };
+                let digits: String = (0..len)
+                    .map(|_| char::from(b'1' + u8::try_from(rng.below(9)).unwrap()))
+                    .collect();
+
+                if left_aligned {
+                    row.push_str(&format!("{digits:<width$}"));
+                } else {
+                    row.push_str(&format!("{digits:>width$}"));
+                }
+            }
+
+            let op = *rng.choose(&['+', '*']).unwrap();
+            rows[operand_rows].push_str(&format!("{op:<width$}"));
+        }
+
+        rows.join("\n") + "\n"
+    }
+}
+
+/// Reads the worksheet token by token for part one, and column by column for part two.
+mod reference {
+    fn apply(op: &str, operands: impl Iterator<Item = u64>) -> u64 {
+        match op {
+            "+" => operands.sum(),
+            "*" => operands.product(),
+            other => panic!("Expected + or *, got {other}"),
+        }
+    }
+
+    pub fn part_one(input: &str) -> Option<u64> {
+        let rows: Vec<Vec<&str>> = input
+            .lines()
+            .map(|line| line.split_whitespace().collect())
+            .collect();
+        let (ops, operands) = rows.split_last()?;
+
+        let res = ops
+            .iter()
+            .enumerate()
+            .map(|(i, op)| apply(op, operands.iter().map(|row| row[i].parse().unwrap())))
+            .sum();
+
+        Some(res)
+    }
+
+    pub fn part_two(input: &str) -> Option<u64> {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let (ops, operands) = lines.split_last()?;
+        let width = lines.iter().map(|line| line.len()).max()?;
+        let at = |line: &[u8], col: usize| line.get(col).copied().unwrap_or(b' ');
+
+        let mut res = 0;
+        let mut numbers = vec![];
+
+        // NOTE: the operator sits below the leftmost column of its problem, which is read last.
+        for col in (0..width).rev() {
+            let digits: String = operands
+                .iter()
+                .map(|line| char::from(at(line, col)))
+                .filter(char::is_ascii_digit)
+                .collect();
+            if !digits.is_empty() {
+                numbers.push(digits.parse().unwrap());
+            }
+
+            let op = at(ops, col);
+            if op != b' ' {
+                res += apply(&char::from(op).to_string(), numbers.drain(..));
+            }
+        }
+
+        Some(res)
+    }
 }
 
 #[cfg(test)]
@@ -92,9 +193,27 @@ mod tests {
         assert_eq!(result, Some(4277556));
     }
 
+    #[test]
+    fn test_columns_with_gaps() {
+        // the right column reads `235`, the left one `1`, a gap and `4`.
+        assert_eq!(part_two("12\n 3\n45\n+ "), Some(235 + 14));
+    }
+
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(3263827));
+    }
+
+    #[test]
+    fn test_random_inputs_match_reference() {
+        let mut rng = Rng::new(6);
+
+        for size in 0..100 {
+            let input = InputGenerator::generate(&mut rng, size);
+
+            assert_eq!(part_one(&input), reference::part_one(&input), "{input}");
+            assert_eq!(part_two(&input), reference::part_two(&input), "{input}");
+        }
     }
 }
@@ -1,7 +1,9 @@
-advent_of_code::solution!(7);
-use std::collections::{HashMap, HashSet};
+advent_of_code::solution!(7, generator = InputGenerator, reference = reference);
+use advent_of_code::grid::Grid;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
 
-#[derive(PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq)]
 enum Cell {
     Empty,
     Splitter,
@@ -19,120 +21,142 @@ impl From<char> for Cell {
     }
 }
 
-struct Grid {
-    cells: Vec<Vec<Cell>>,
+/// Splits and timelines of the beam, counted by sweeping the manifold one row at a time.
+struct Sweep {
+    /// Number of splitters that were hit by a beam.
+    splits: u64,
+    /// Number of ways a beam can leave the bottom of the manifold, or [`None`] if there are more than fit into a `u128`.
+    timelines: Option<u128>,
 }
 
-impl Grid {
-    fn from_input(input: &str) -> Self {
-        let cells: Vec<Vec<Cell>> = input
-            .lines()
-            .map(|line| line.chars().map(|c| Cell::from(c)).collect())
-            .collect();
-
-        Self { cells }
-    }
-
-    fn ncols(&self) -> usize {
-        self.cells[0].len()
-    }
-
-    fn get(&self, row: usize, col: usize) -> Option<&Cell> {
-        self.cells.get(row).and_then(|r| r.get(col))
-    }
+/// Adds timeline counts, where [`None`] is a count that has already overflowed.
+fn add_timelines(a: Option<u128>, b: Option<u128>) -> Option<u128> {
+    a?.checked_add(b?)
+}
 
-    fn iter(&self) -> impl Iterator<Item = (usize, usize, &Cell)> {
-        self.cells.iter().enumerate().flat_map(|(row, cells)| {
-            cells
-                .iter()
-                .enumerate()
-                .map(move |(col, cell)| (row, col, cell))
-        })
-    }
+fn sweep(grid: &Grid<Cell>) -> Option<Sweep> {
+    let (start_row, start_col) = grid.position(&Cell::Start)?;
 
-    fn find_start(&self) -> (usize, usize) {
-        let (row, col, _) = self
-            .iter()
-            .find(|(_, _, cell)| matches!(cell, Cell::Start))
-            .unwrap();
+    // number of timelines in which a beam is in each column of the current row.
+    let mut beams: Vec<Option<u128>> = vec![Some(0); grid.width()];
+    beams[start_col] = Some(1);
+    let mut splits = 0;
 
-        (row, col)
-    }
-
-    fn go_down(&self, row: usize, col: usize, cache: &mut HashSet<(usize, usize)>) -> u64 {
-        if col >= self.ncols() || !cache.insert((row, col)) {
-            return 0;
-        }
+    for row in start_row + 1..grid.height() {
+        let mut next = vec![Some(0); grid.width()];
+        let mut add = |col: usize, count: Option<u128>| {
+            if let Some(total) = next.get_mut(col) {
+                *total = add_timelines(*total, count);
+            }
+        };
 
-        let nrow = row + 1;
+        for (col, &count) in beams.iter().enumerate() {
+            if count == Some(0) {
+                continue;
+            }
 
-        match self.get(nrow, col) {
-            Some(Cell::Splitter) => {
-                let left = col
-                    .checked_sub(1)
-                    .map_or(0, |c| self.go_down(nrow, c, cache));
-                let right = self.go_down(nrow, col + 1, cache);
-                1 + left + right
+            if grid[(row, col)] == Cell::Splitter {
+                splits += 1;
+                // NOTE: beams that are split past the edge of the manifold are lost.
+                if let Some(left) = col.checked_sub(1) {
+                    add(left, count);
+                }
+                add(col + 1, count);
+            } else {
+                add(col, count);
             }
-            Some(Cell::Empty) => self.go_down(nrow, col, cache),
-            Some(Cell::Start) => 0,
-            None => 0,
         }
+
+        beams = next;
     }
 
-    // we need the cache here to memoize the paths
-    // the keys are the positions (i.e. a position starting from row 1, col 5)
-    // values are the number of paths from that position, to prevent recalculating and exponential complexity
-    fn timelines_count(
-        &self,
-        row: usize,
-        col: usize,
-        cache: &mut HashMap<(usize, usize), u64>,
-    ) -> u64 {
-        if col >= self.ncols() {
-            return 0;
-        }
+    let timelines = beams.into_iter().fold(Some(0), add_timelines);
+    Some(Sweep { splits, timelines })
+}
 
-        if let Some(&cached) = cache.get(&(row, col)) {
-            return cached;
-        }
+pub fn part_one(input: &str) -> Option<u64> {
+    let grid: Grid<Cell> = Grid::parse(input).unwrap();
+    Some(sweep(&grid)?.splits)
+}
 
-        let nrow = row + 1;
+pub fn part_two(input: &str) -> Option<u128> {
+    let grid: Grid<Cell> = Grid::parse(input).unwrap();
+    sweep(&grid)?.timelines
+}
 
-        let result = match self.get(nrow, col) {
-            Some(Cell::Splitter) => {
-                let left = col
-                    .checked_sub(1)
-                    .map_or(0, |c| self.timelines_count(nrow, c, cache));
-                let right = self.timelines_count(nrow, col + 1, cache);
-                left + right
-            }
-            Some(Cell::Empty | Cell::Start) => self.timelines_count(nrow, col, cache),
-            None => 1,
-        };
+struct InputGenerator;
 
-        cache.insert((row, col), result);
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = 1 + 2 * size;
+        let mut lines = vec![format!("{}S{}", ".".repeat(size), ".".repeat(size))];
 
-        result
+        for _ in 0..size {
+            lines.push(".".repeat(width));
+            lines.push(
+                (0..width)
+                    .map(|_| if rng.chance(1, 3) { '^' } else { '.' })
+                    .collect(),
+            );
+        }
+
+        lines.join("\n")
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let grid = Grid::from_input(input);
-    let (row, col) = grid.find_start();
+/// Searches the graph of beam positions.
+mod reference {
+    use super::{Cell, Grid};
+    use advent_of_code::geometry::{Direction4, Point2};
+    use advent_of_code::graph;
+
+    /// Positions the beam continues at from `pos`, splitters split it to their left and right.
+    pub fn beam_successors(grid: &Grid<Cell>, pos: Point2) -> Vec<Point2> {
+        let next = pos + Direction4::Down;
+
+        match grid.get_point(next) {
+            Some(Cell::Splitter) => [next + Direction4::Left, next + Direction4::Right]
+                .into_iter()
+                .filter(|p| grid.contains_point(*p))
+                .collect(),
+            Some(Cell::Empty | Cell::Start) => vec![next],
+            None => vec![],
+        }
+    }
 
-    Some(grid.go_down(row, col, &mut HashSet::new()))
-}
+    pub fn search(grid: &Grid<Cell>) -> (u64, u128) {
+        let (row, col) = grid.position(&Cell::Start).unwrap();
+        let start = Point2::from_grid(row, col);
+        let below = |pos: &Point2| grid.get_point(*pos + Direction4::Down);
+
+        let beams = graph::bfs(start, |pos| beam_successors(grid, *pos));
+        let splits = beams
+            .reached()
+            .filter(|pos| below(pos) == Some(&Cell::Splitter))
+            .count();
+
+        let timelines = graph::count_paths(
+            start,
+            |pos| beam_successors(grid, *pos),
+            |pos| below(pos).is_none(),
+        )
+        .unwrap();
+
+        (splits as u64, timelines)
+    }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let grid = Grid::from_input(input);
-    let (row, col) = grid.find_start();
+    pub fn part_one(input: &str) -> Option<u64> {
+        Some(search(&Grid::parse(input).unwrap()).0)
+    }
 
-    Some(grid.timelines_count(row, col, &mut HashMap::new()))
+    pub fn part_two(input: &str) -> Option<u128> {
+        Some(search(&Grid::parse(input).unwrap()).1)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::search;
     use super::*;
 
     #[test]
@@ -146,4 +170,47 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(40));
     }
+
+    #[test]
+    fn test_random_grids_match_search() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..100 {
+            let width = 1 + rng.index(20);
+            let height = 1 + rng.index(30);
+            let mut cells: Vec<Cell> = (0..width * height)
+                .map(|_| {
+                    if rng.chance(1, 4) {
+                        Cell::Splitter
+                    } else {
+                        Cell::Empty
+                    }
+                })
+                .collect();
+            cells[rng.index(width)] = Cell::Start;
+            let grid = Grid::from_vec(width, cells).unwrap();
+
+            let result = sweep(&grid).unwrap();
+            assert_eq!(
+                (result.splits, result.timelines.unwrap()),
+                search(&grid),
+                "{grid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_timelines_beyond_u64() {
+        // every splitter row doubles the timelines, the grid is wide enough that no beam is lost.
+        let width = 201;
+        let mut lines = vec![format!("{}S{}", ".".repeat(100), ".".repeat(100))];
+        for _ in 0..70 {
+            lines.push(".".repeat(width));
+            lines.push("^".repeat(width));
+        }
+        let input = lines.join("\n");
+
+        assert_eq!(part_one(&input), Some(70 * 71 / 2));
+        assert_eq!(part_two(&input), Some(1 << 70));
+    }
 }
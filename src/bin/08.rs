@@ -1,141 +1,125 @@
-use std::collections::HashSet;
-
-advent_of_code::solution!(8);
-
-struct UnionFind {
-    parent: Vec<usize>,
-    size: Vec<usize>,
-}
-
-impl UnionFind {
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            size: vec![1; n],
-        }
-    }
-
-    fn find(&mut self, x: usize) -> usize {
-        if self.parent[x] != x {
-            self.parent[x] = self.find(self.parent[x]);
-        }
-
-        self.parent[x]
-    }
-
-    fn union(&mut self, x: usize, y: usize) {
-        let px = self.find(x);
-        let py = self.find(y);
-
-        if px != py {
-            if self.size[px] < self.size[py] {
-                self.parent[px] = py;
-                self.size[py] += self.size[px];
-            } else {
-                self.parent[py] = px;
-                self.size[px] += self.size[py];
-            }
-        }
-    }
-
-    fn circuit_sizes(&mut self) -> Vec<usize> {
-        let mut roots = HashSet::new();
-
-        for i in 0..self.parent.len() {
-            roots.insert(self.find(i));
-        }
-
-        roots.iter().map(|&r| self.size[r]).collect()
-    }
-}
-
-struct JunctionBox {
-    x: u64,
-    y: u64,
-    z: u64,
-}
-
-impl JunctionBox {
-    fn from_line(line: &str) -> Self {
-        let mut nums = line.split(',').map(|n| n.parse().unwrap());
-        Self {
-            x: nums.next().unwrap(),
-            y: nums.next().unwrap(),
-            z: nums.next().unwrap(),
-        }
-    }
-
-    fn distance(&self, other: &JunctionBox) -> u64 {
-        self.x.abs_diff(other.x).pow(2)
-            + self.y.abs_diff(other.y).pow(2)
-            + self.z.abs_diff(other.z).pow(2)
-    }
-}
+use advent_of_code::checked::Checked;
+use advent_of_code::disjoint_set::DisjointSet;
+use advent_of_code::geometry::Point3;
+use advent_of_code::kd_tree::KdTree;
+use advent_of_code::parse;
+use advent_of_code::rng::Rng;
+use advent_of_code::template::generator::Generator;
+
+advent_of_code::solution!(
+    8,
+    parse = parse_input,
+    generator = InputGenerator,
+    reference = reference
+);
+
+type JunctionBox = Point3;
 
 fn parse_input(input: &str) -> Vec<JunctionBox> {
-    input.lines().map(JunctionBox::from_line).collect()
+    parse::lines(input)
+        .collect::<Result<_, _>>()
+        .unwrap_or_else(|e| panic!("{e}"))
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let boxes = parse_input(input);
+    let boxes = KdTree::new(parse_input(input));
     let n = boxes.len();
 
-    let mut edges: Vec<(usize, usize, u64)> = Vec::new();
-
-    for i in 0..n {
-        for j in i + 1..n {
-            edges.push((i, j, boxes[i].distance(&boxes[j])));
-        }
-    }
-
-    edges.sort_unstable_by_key(|e| e.2);
-
     // to work with example and puzzle input
     let num_connections = if n <= 20 { 10 } else { 1000 };
 
-    let mut union_find = UnionFind::new(n);
+    let mut circuits = DisjointSet::new(n);
 
-    for &(i, j, _) in edges.iter().take(num_connections) {
-        union_find.union(i, j);
+    for (i, j, _) in boxes.edges_by_distance().take(num_connections) {
+        circuits.union(i, j);
     }
 
-    let mut sizes = union_find.circuit_sizes();
+    let mut sizes = circuits.component_sizes();
     sizes.sort_unstable();
 
     Some(sizes.iter().rev().take(3).map(|&s| s as u64).product())
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let boxes = parse_input(input);
-    let n = boxes.len();
+    let boxes = KdTree::new(parse_input(input));
+    let points = boxes.points();
 
-    let mut edges: Vec<(usize, usize, u64)> = Vec::new();
+    let mut circuits = DisjointSet::new(boxes.len());
 
-    for i in 0..n {
-        for j in i + 1..n {
-            edges.push((i, j, boxes[i].distance(&boxes[j])));
+    // Kruskal's algorithm, the last edge of the spanning tree joins the final two circuits.
+    for (i, j, _) in boxes.edges_by_distance() {
+        if circuits.union(i, j) && circuits.component_count() == 1 {
+            let product = Checked(points[i].x) * Checked(points[j].x);
+            return u64::try_from(product.get()).ok();
         }
     }
 
-    edges.sort_unstable_by_key(|e| e.2);
+    None
+}
 
-    let mut union_find = UnionFind::new(n);
+struct InputGenerator;
+
+impl Generator for InputGenerator {
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..2 + size)
+            .map(|_| {
+                let (x, y, z) = (
+                    rng.range(0..=100_000),
+                    rng.range(0..=100_000),
+                    rng.range(0..=100_000),
+                );
+                Point3::new(x, y, z).to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
 
-    for &(i, j, _) in edges.iter() {
-        if union_find.find(i) != union_find.find(j) {
-            union_find.union(i, j);
+/// Sorts all pairs of boxes by their distance.
+mod reference {
+    use super::{DisjointSet, JunctionBox, parse_input};
 
-            if union_find.circuit_sizes().len() == 1 {
-                return Some(boxes[i].x * boxes[j].x);
+    fn edges_by_distance(boxes: &[JunctionBox]) -> Vec<(u128, usize, usize)> {
+        let mut edges = vec![];
+        for i in 0..boxes.len() {
+            for j in i + 1..boxes.len() {
+                edges.push((boxes[i].distance_squared(boxes[j]), i, j));
             }
         }
+        edges.sort_unstable();
+        edges
     }
 
-    None
+    pub fn part_one(input: &str) -> Option<u64> {
+        let boxes = parse_input(input);
+        let num_connections = if boxes.len() <= 20 { 10 } else { 1000 };
+
+        let mut circuits = DisjointSet::new(boxes.len());
+        for (_, i, j) in edges_by_distance(&boxes).into_iter().take(num_connections) {
+            circuits.union(i, j);
+        }
+
+        let mut sizes = circuits.component_sizes();
+        sizes.sort_unstable();
+        Some(sizes.iter().rev().take(3).map(|&s| s as u64).product())
+    }
+
+    pub fn part_two_brute_force(boxes: &[JunctionBox]) -> Option<i64> {
+        let mut circuits = DisjointSet::new(boxes.len());
+        edges_by_distance(boxes).into_iter().find_map(|(_, i, j)| {
+            (circuits.union(i, j) && circuits.component_count() == 1)
+                .then(|| boxes[i].x * boxes[j].x)
+        })
+    }
+
+    pub fn part_two(input: &str) -> Option<u64> {
+        part_two_brute_force(&parse_input(input)).and_then(|x| u64::try_from(x).ok())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::reference::part_two_brute_force;
     use super::*;
 
     #[test]
@@ -149,4 +133,29 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(25272));
     }
+
+    #[test]
+    fn test_part_two_matches_brute_force() {
+        let mut rng = Rng::new(11);
+
+        for _ in 0..20 {
+            let boxes: Vec<JunctionBox> = (0..2 + rng.index(300))
+                .map(|_| {
+                    Point3::new(
+                        rng.range(0..=1000),
+                        rng.range(0..=1000),
+                        rng.range(0..=1000),
+                    )
+                })
+                .collect();
+            let input = boxes
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let expected = part_two_brute_force(&boxes).map(|x| u64::try_from(x).unwrap());
+            assert_eq!(part_two(&input), expected);
+        }
+    }
 }
@@ -0,0 +1,144 @@
+//! Integers that panic on overflow in every build profile.
+//!
+//! Release builds wrap silently on overflow, which turns a bug into a plausible but wrong answer.
+//! Wrapping the numbers that make up an answer in [`Checked`] makes such bugs fail loudly instead.
+//! To enable overflow checks for all arithmetic of a solution, run it with `cargo solve NN --checked`.
+
+use std::fmt::{self, Display};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+
+/// An integer whose arithmetic panics on overflow, even in release builds.
+///
+/// ```ignore
+/// let total: Checked<u64> = values.iter().copied().map(Checked).sum();
+/// Some(total.get())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Checked<T>(pub T);
+
+impl<T> Checked<T> {
+    pub fn get(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Checked<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Display> Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($t:ty, $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $checked:ident, $symbol:literal) => {
+        impl $trait for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: Self) -> Self {
+                match self.0.$checked(rhs.0) {
+                    Some(result) => Self(result),
+                    None => panic!(
+                        "arithmetic overflow: {} {} {} does not fit into {}",
+                        self.0,
+                        $symbol,
+                        rhs.0,
+                        stringify!($t)
+                    ),
+                }
+            }
+        }
+
+        impl $trait<$t> for Checked<$t> {
+            type Output = Self;
+
+            #[track_caller]
+            fn $method(self, rhs: $t) -> Self {
+                self.$method(Self(rhs))
+            }
+        }
+
+        impl $assign_trait for Checked<$t> {
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = self.$method(rhs);
+            }
+        }
+
+        impl $assign_trait<$t> for Checked<$t> {
+            #[track_caller]
+            fn $assign_method(&mut self, rhs: $t) {
+                *self = self.$method(Self(rhs));
+            }
+        }
+    };
+}
+
+macro_rules! impl_checked {
+    ($($t:ty),*) => {
+        $(
+            impl_checked_op!($t, Add, add, AddAssign, add_assign, checked_add, "+");
+            impl_checked_op!($t, Sub, sub, SubAssign, sub_assign, checked_sub, "-");
+            impl_checked_op!($t, Mul, mul, MulAssign, mul_assign, checked_mul, "*");
+
+            impl Sum for Checked<$t> {
+                #[track_caller]
+                fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self(0), |a, b| a + b)
+                }
+            }
+
+            impl Product for Checked<$t> {
+                #[track_caller]
+                fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self(1), |a, b| a * b)
+                }
+            }
+        )*
+    };
+}
+
+impl_checked!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Checked;
+
+    #[test]
+    fn computes_without_overflow() {
+        assert_eq!(Checked(2_u64) + Checked(3_u64) * 4, Checked(14));
+        assert_eq!(Checked(-5_i64) - 7, Checked(-12));
+
+        let mut value = Checked(10_u8);
+        value -= 4;
+        value *= Checked(3);
+        assert_eq!(value.get(), 18);
+
+        let sum: Checked<u64> = [1, 2, 3].into_iter().map(Checked).sum();
+        let product: Checked<u64> = [1, 2, 3, 4].into_iter().map(Checked).product();
+        assert_eq!((sum.get(), product.get()), (6, 24));
+        assert_eq!(Checked(42_i32).to_string(), "42");
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow: 200 + 100 does not fit into u8")]
+    fn panics_on_overflow() {
+        let _ = Checked(200_u8) + 100;
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into u64")]
+    fn panics_on_overflowing_sum() {
+        let _: Checked<u64> = [u64::MAX, 1].into_iter().map(Checked).sum();
+    }
+}
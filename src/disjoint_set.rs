@@ -0,0 +1,162 @@
+//! A disjoint-set forest (union-find) over the elements `0..n`.
+
+use std::collections::HashMap;
+
+/// Partitions the elements `0..n` into disjoint components that can be merged.
+///
+/// Uses path compression and union by size, so operations run in nearly constant amortized time.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// Creates a set of `n` elements, each in its own component.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            components: n,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative of the component that contains `x`.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        // NOTE: compress iteratively, long chains would overflow the stack when recursing.
+        let mut current = x;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+
+        root
+    }
+
+    /// Merges the components that contain `x` and `y`.
+    /// Returns `false` if both were already in the same component.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut rx, mut ry) = (self.find(x), self.find(y));
+
+        if rx == ry {
+            return false;
+        }
+
+        if self.size[rx] < self.size[ry] {
+            std::mem::swap(&mut rx, &mut ry);
+        }
+
+        self.parent[ry] = rx;
+        self.size[rx] += self.size[ry];
+        self.components -= 1;
+
+        true
+    }
+
+    /// Whether `x` and `y` are in the same component.
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /// Number of components.
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Number of elements in the component that contains `x`.
+    pub fn component_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Sizes of all components, in no particular order.
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&x| self.parent[x] == x)
+            .map(|root| self.size[root])
+            .collect()
+    }
+
+    /// Elements grouped by component, ordered by their smallest element.
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root: HashMap<usize, usize> = HashMap::with_capacity(self.components);
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+
+        for x in 0..self.len() {
+            let root = self.find(x);
+            let index = *index_of_root.entry(root).or_insert_with(|| {
+                groups.push(Vec::with_capacity(self.size[root]));
+                groups.len() - 1
+            });
+            groups[index].push(x);
+        }
+
+        groups
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DisjointSet;
+
+    #[test]
+    fn merges_components() {
+        let mut set = DisjointSet::new(5);
+        assert_eq!(set.component_count(), 5);
+
+        assert!(set.union(0, 1));
+        assert!(set.union(3, 4));
+        assert!(set.union(1, 4));
+        assert!(!set.union(0, 3));
+
+        assert_eq!(set.component_count(), 2);
+        assert!(set.same(0, 3));
+        assert!(!set.same(2, 3));
+        assert_eq!(set.component_size(4), 4);
+        assert_eq!(set.component_size(2), 1);
+    }
+
+    #[test]
+    fn groups_components() {
+        let mut set = DisjointSet::new(6);
+        set.union(5, 0);
+        set.union(2, 4);
+        set.union(4, 0);
+
+        assert_eq!(set.groups(), vec![vec![0, 2, 4, 5], vec![1], vec![3]]);
+
+        let mut sizes = set.component_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 4]);
+    }
+
+    #[test]
+    fn handles_long_chains() {
+        let n = 100_000;
+        let mut set = DisjointSet::new(n);
+        for x in 1..n {
+            set.union(x, x - 1);
+        }
+        assert_eq!(set.component_count(), 1);
+        assert_eq!(set.component_size(0), n);
+    }
+}
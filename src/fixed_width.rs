@@ -0,0 +1,253 @@
+//! Parsing of inputs where the position of a character within its line matters,
+//! such as worksheets with numbers written in aligned columns.
+//!
+//! ```text
+//! 123 328
+//!  45 64
+//! *   +
+//! ```
+//!
+//! The input is read as a [`CharMatrix`], which is split into [`Block`]s at columns that are blank in every line.
+
+use std::fmt::{self, Display};
+use std::ops::Range;
+
+use crate::grid::Grid;
+
+/// The characters of an input, padded with spaces to a rectangle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharMatrix {
+    grid: Grid<char>,
+}
+
+impl CharMatrix {
+    /// Reads `input` line by line. Lines shorter than the longest one are padded with spaces,
+    /// so trailing whitespace that was trimmed from the input does not shift any columns.
+    pub fn new(input: &str) -> Self {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut grid = Grid::new(width, lines.len(), ' ');
+        for (row, line) in lines.iter().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                grid[(row, col)] = c;
+            }
+        }
+
+        Self { grid }
+    }
+
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    /// The whole matrix as a single block.
+    pub fn as_block(&self) -> Block<'_> {
+        Block {
+            grid: &self.grid,
+            rows: 0..self.height(),
+            cols: 0..self.width(),
+        }
+    }
+
+    /// Whether column `col` only contains whitespace.
+    pub fn is_blank_column(&self, col: usize) -> bool {
+        self.grid.column(col).all(|c| c.is_whitespace())
+    }
+
+    /// Iterates the blocks between blank columns from left to right.
+    /// Consecutive blank columns count as a single separator, and blank columns at the edges are skipped.
+    pub fn blocks(&self) -> impl Iterator<Item = Block<'_>> {
+        let mut col = 0;
+
+        std::iter::from_fn(move || {
+            while col < self.width() && self.is_blank_column(col) {
+                col += 1;
+            }
+
+            if col == self.width() {
+                return None;
+            }
+
+            let start = col;
+            while col < self.width() && !self.is_blank_column(col) {
+                col += 1;
+            }
+
+            Some(Block {
+                grid: &self.grid,
+                rows: 0..self.height(),
+                cols: start..col,
+            })
+        })
+    }
+}
+
+/// A rectangular part of a [`CharMatrix`]. Rows and columns are indexed relative to the block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block<'a> {
+    grid: &'a Grid<char>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<'a> Block<'a> {
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The columns of the matrix that the block spans.
+    pub fn span(&self) -> Range<usize> {
+        self.cols.clone()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<char> {
+        if row < self.height() && col < self.width() {
+            Some(self.grid[(self.rows.start + row, self.cols.start + col)])
+        } else {
+            None
+        }
+    }
+
+    /// The characters of row `row`, including any padding.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> String {
+        assert!(row < self.height(), "row {row} is out of bounds");
+        self.grid.row(self.rows.start + row)[self.cols.clone()]
+            .iter()
+            .collect()
+    }
+
+    /// Iterates the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.height()).map(|row| self.row(row))
+    }
+
+    /// The characters of column `col`, read from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn column(&self, col: usize) -> String {
+        assert!(col < self.width(), "column {col} is out of bounds");
+        let col = self.cols.start + col;
+        self.rows.clone().map(|row| self.grid[(row, col)]).collect()
+    }
+
+    /// Iterates the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.width()).map(|col| self.column(col))
+    }
+
+    /// Iterates the columns from right to left.
+    pub fn columns_rtl(&self) -> impl Iterator<Item = String> + '_ {
+        (0..self.width()).rev().map(|col| self.column(col))
+    }
+
+    /// The block restricted to `rows`, relative to this block.
+    ///
+    /// # Panics
+    /// Panics if `rows` is out of bounds.
+    #[must_use]
+    pub fn with_rows(&self, rows: Range<usize>) -> Self {
+        assert!(
+            rows.start <= rows.end && rows.end <= self.height(),
+            "rows {rows:?} are out of bounds"
+        );
+        Self {
+            grid: self.grid,
+            rows: self.rows.start + rows.start..self.rows.start + rows.end,
+            cols: self.cols.clone(),
+        }
+    }
+
+    /// Splits off the last row, e.g. a row of operators below the operands.
+    /// Returns [`None`] if the block has no rows.
+    pub fn split_last_row(&self) -> Option<(Self, Self)> {
+        let last = self.height().checked_sub(1)?;
+        Some((self.with_rows(0..last), self.with_rows(last..self.height())))
+    }
+}
+
+impl Display for Block<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..self.height() {
+            if row > 0 {
+                writeln!(f)?;
+            }
+            f.write_str(&self.row(row))?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::CharMatrix;
+
+    const WORKSHEET: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
+
+    #[test]
+    fn pads_ragged_lines() {
+        let matrix = CharMatrix::new("ab\r\nc\n\ndef");
+        assert_eq!(matrix.width(), 3);
+        assert_eq!(matrix.height(), 4);
+        assert_eq!(matrix.as_block().to_string(), "ab \nc  \n   \ndef");
+    }
+
+    #[test]
+    fn splits_at_blank_columns() {
+        let matrix = CharMatrix::new(WORKSHEET);
+        let spans: Vec<_> = matrix.blocks().map(|b| b.span()).collect();
+        assert_eq!(spans, vec![0..3, 4..7, 8..11, 12..15]);
+
+        let matrix = CharMatrix::new("  a  b\n  a   ");
+        let spans: Vec<_> = matrix.blocks().map(|b| b.span()).collect();
+        assert_eq!(spans, vec![2..3, 5..6]);
+
+        assert_eq!(CharMatrix::new("").blocks().count(), 0);
+    }
+
+    #[test]
+    fn reads_rows_and_columns() {
+        let matrix = CharMatrix::new(WORKSHEET);
+        let block = matrix.blocks().last().unwrap();
+        let (operands, operator) = block.split_last_row().unwrap();
+
+        assert_eq!(operator.row(0).trim(), "+");
+        assert_eq!(
+            operands.rows().collect::<Vec<_>>(),
+            vec!["64 ", "23 ", "314"]
+        );
+        assert_eq!(
+            operands.columns_rtl().collect::<Vec<_>>(),
+            vec!["  4", "431", "623"]
+        );
+        assert_eq!(operands.column(0), "623");
+        assert_eq!(operands.get(2, 2), Some('4'));
+        assert_eq!(operands.get(3, 0), None);
+    }
+}
@@ -0,0 +1,467 @@
+//! Points and directions in two and three dimensions.
+//!
+//! In two dimensions, `x` is the column and `y` is the row, so `y` grows downwards as in puzzle inputs.
+
+use std::fmt::{self, Display};
+use std::num::ParseIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::checked::Checked;
+
+/// A point or offset on a two-dimensional plane.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// A point or offset in three-dimensional space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point2 {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// Creates the point of a grid position.
+    ///
+    /// # Panics
+    /// Panics if the position does not fit into an `i64`.
+    pub fn from_grid(row: usize, col: usize) -> Self {
+        let coordinate = |v: usize| i64::try_from(v).expect("grid position fits into i64");
+        Self::new(coordinate(col), coordinate(row))
+    }
+
+    /// Grid position `(row, col)` of the point, or [`None`] if a coordinate is negative.
+    pub fn to_grid(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.y).ok()?, usize::try_from(self.x).ok()?))
+    }
+
+    /// Sum of the absolute differences of the coordinates.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Largest absolute difference of the coordinates, i.e. the number of king moves between the points.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Squared euclidean distance, which orders points like the euclidean distance without rounding.
+    ///
+    /// # Panics
+    /// Panics if the distance does not fit into a `u128`, which needs coordinates more than 2⁶³ apart.
+    pub fn distance_squared(self, other: Self) -> u128 {
+        (Checked(square_diff(self.x, other.x)) + square_diff(self.y, other.y)).get()
+    }
+
+    /// Iterates the four orthogonal neighbors, clockwise starting with up.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Direction4::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// Iterates all eight neighbors, clockwise starting with up.
+    pub fn neighbors8(self) -> impl Iterator<Item = Self> {
+        Direction8::ALL.into_iter().map(move |d| self + d)
+    }
+}
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Sum of the absolute differences of the coordinates.
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Largest absolute difference of the coordinates.
+    pub fn chebyshev(self, other: Self) -> u64 {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Squared euclidean distance, which orders points like the euclidean distance without rounding.
+    ///
+    /// # Panics
+    /// Panics if the distance does not fit into a `u128`, which needs coordinates more than 2⁶³ apart.
+    pub fn distance_squared(self, other: Self) -> u128 {
+        let squares = Checked(square_diff(self.x, other.x)) + square_diff(self.y, other.y);
+        (squares + square_diff(self.z, other.z)).get()
+    }
+}
+
+/// Square of the difference of two coordinates, which always fits into a `u128`.
+fn square_diff(a: i64, b: i64) -> u128 {
+    u128::from(a.abs_diff(b)).pow(2)
+}
+
+macro_rules! impl_point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        impl Add for $point {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                Self { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $point {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                Self { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i64> for $point {
+            type Output = Self;
+
+            fn mul(self, rhs: i64) -> Self {
+                Self { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $point {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $point {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $point {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        /// Parses comma-separated coordinates, e.g. `1,-2`.
+        impl FromStr for $point {
+            type Err = PointParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut coordinates = s.split(',').map(|c| c.trim().parse::<i64>());
+                let point = Self {
+                    $($field: coordinates.next().ok_or(PointParseError::Dimensions)??),+
+                };
+                match coordinates.next() {
+                    Some(_) => Err(PointParseError::Dimensions),
+                    None => Ok(point),
+                }
+            }
+        }
+    };
+}
+
+impl_point_ops!(Point2 { x, y });
+impl_point_ops!(Point3 { x, y, z });
+
+impl Display for Point2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// An error which can be returned when parsing a [`Point2`] or [`Point3`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointParseError {
+    /// The number of coordinates does not match the dimension of the point.
+    Dimensions,
+    Coordinate(ParseIntError),
+}
+
+impl From<ParseIntError> for PointParseError {
+    fn from(e: ParseIntError) -> Self {
+        Self::Coordinate(e)
+    }
+}
+
+impl Display for PointParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Dimensions => f.write_str("wrong number of coordinates"),
+            Self::Coordinate(e) => write!(f, "invalid coordinate: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for PointParseError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four orthogonal directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions, clockwise starting with up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The offset of a single step in this direction.
+    pub const fn delta(self) -> Point2 {
+        match self {
+            Self::Up => Point2::new(0, -1),
+            Self::Right => Point2::new(1, 0),
+            Self::Down => Point2::new(0, 1),
+            Self::Left => Point2::new(-1, 0),
+        }
+    }
+
+    /// Turns by 90° clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    /// Turns by 90° counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        self.reverse().turn_right()
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+/// Parses arrows (`^>v<`) and letters (`URDL`).
+impl TryFrom<char> for Direction4 {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' => Ok(Self::Up),
+            '>' | 'R' => Ok(Self::Right),
+            'v' | 'D' => Ok(Self::Down),
+            '<' | 'L' => Ok(Self::Left),
+            other => Err(other),
+        }
+    }
+}
+
+/// One of the eight directions on a grid, named after compass points with north pointing up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting with north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// The offset of a single step in this direction.
+    pub const fn delta(self) -> Point2 {
+        match self {
+            Self::N => Point2::new(0, -1),
+            Self::NE => Point2::new(1, -1),
+            Self::E => Point2::new(1, 0),
+            Self::SE => Point2::new(1, 1),
+            Self::S => Point2::new(0, 1),
+            Self::SW => Point2::new(-1, 1),
+            Self::W => Point2::new(-1, 0),
+            Self::NW => Point2::new(-1, -1),
+        }
+    }
+
+    /// Turns by 45° clockwise.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns by 45° counterclockwise.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(value: Direction4) -> Self {
+        match value {
+            Direction4::Up => Self::N,
+            Direction4::Right => Self::E,
+            Direction4::Down => Self::S,
+            Direction4::Left => Self::W,
+        }
+    }
+}
+
+impl From<Direction4> for Point2 {
+    fn from(value: Direction4) -> Self {
+        value.delta()
+    }
+}
+
+impl From<Direction8> for Point2 {
+    fn from(value: Direction8) -> Self {
+        value.delta()
+    }
+}
+
+impl Add<Direction4> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Direction4) -> Self {
+        self + rhs.delta()
+    }
+}
+
+impl Add<Direction8> for Point2 {
+    type Output = Self;
+
+    fn add(self, rhs: Direction8) -> Self {
+        self + rhs.delta()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction4, Direction8, Point2, Point3, PointParseError};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 7));
+        assert_eq!(a - b, Point2::new(4, -3));
+        assert_eq!(-a * 2, Point2::new(-2, -4));
+        assert_eq!(a + Direction4::Up, Point2::new(1, 1));
+        assert_eq!(a + Direction8::SW, Point2::new(0, 3));
+
+        let mut c = Point3::new(1, 2, 3);
+        c += Point3::new(1, 1, 1);
+        c -= Point3::new(0, 0, 4);
+        assert_eq!(c, Point3::new(2, 3, 0));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+
+        let c = Point3::new(162, 817, 812);
+        let d = Point3::new(425, 690, 689);
+        assert_eq!(c.manhattan(d), 263 + 127 + 123);
+        assert_eq!(c.chebyshev(d), 263);
+        assert_eq!(c.distance_squared(d), 263 * 263 + 127 * 127 + 123 * 123);
+    }
+
+    #[test]
+    fn measures_large_distances() {
+        let (low, high) = (-(1 << 40), 1 << 40);
+        let a = Point3::new(low, low, low);
+        let b = Point3::new(high, high, high);
+        assert_eq!(a.distance_squared(b), 3 << 82);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit into u128")]
+    fn panics_if_distance_overflows() {
+        let _ = Point2::new(i64::MIN, i64::MIN).distance_squared(Point2::new(i64::MAX, i64::MAX));
+    }
+
+    #[test]
+    fn converts_grid_positions() {
+        let p = Point2::from_grid(3, 5);
+        assert_eq!(p, Point2::new(5, 3));
+        assert_eq!(p.to_grid(), Some((3, 5)));
+        assert_eq!(Point2::new(-1, 0).to_grid(), None);
+    }
+
+    #[test]
+    fn parses_points() {
+        assert_eq!("1,-2".parse::<Point2>(), Ok(Point2::new(1, -2)));
+        assert_eq!("1, 2, 3".parse::<Point3>(), Ok(Point3::new(1, 2, 3)));
+        assert_eq!("1,2".parse::<Point3>(), Err(PointParseError::Dimensions));
+        assert_eq!("1,2,3".parse::<Point2>(), Err(PointParseError::Dimensions));
+        assert!(matches!(
+            "1,x".parse::<Point2>(),
+            Err(PointParseError::Coordinate(_))
+        ));
+        assert_eq!(Point3::new(1, 2, 3).to_string(), "1,2,3");
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Right.reverse(), Direction4::Left);
+        assert_eq!(Direction8::NW.turn_right(), Direction8::N);
+        assert_eq!(Direction8::N.turn_left(), Direction8::NW);
+        assert_eq!(Direction8::NE.reverse(), Direction8::SW);
+        assert_eq!(Direction8::from(Direction4::Down), Direction8::S);
+        assert_eq!(Direction4::try_from('v'), Ok(Direction4::Down));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let p = Point2::ORIGIN;
+        assert_eq!(p.neighbors4().count(), 4);
+        assert!(p.neighbors8().all(|n| n.chebyshev(p) == 1));
+        assert_eq!(p.neighbors4().map(|n| n.manhattan(p)).sum::<u64>(), 4);
+    }
+}
@@ -0,0 +1,363 @@
+//! Iterative graph searches over implicit graphs.
+//!
+//! Graphs are described by a `successors` closure that returns the neighbors of a node,
+//! so the nodes can be grid positions, points or any other hashable state.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Distances and predecessors of all nodes that a search reached from its start.
+#[derive(Debug, Clone)]
+pub struct SearchResult<N, C> {
+    pub start: N,
+    pub distances: HashMap<N, C>,
+    /// The node each node was reached from on a shortest path. The start has no predecessor.
+    pub predecessors: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> SearchResult<N, C> {
+    /// Distance from the start to `node`, or [`None`] if it was not reached.
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// Whether `node` was reached.
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    /// A shortest path from the start to `node`, including both.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = self.predecessors.get(path.last()?) {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Iterates all reached nodes, in no particular order.
+    pub fn reached(&self) -> impl Iterator<Item = &N> {
+        self.distances.keys()
+    }
+}
+
+/// Breadth-first search, where every edge has length `1`.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut predecessors = HashMap::new();
+    let mut queue = VecDeque::from([start.clone()]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node] + 1;
+
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance);
+                predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    SearchResult {
+        start,
+        distances,
+        predecessors,
+    }
+}
+
+/// Dijkstra's algorithm, for successors that are returned with the non-negative cost of the edge.
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> SearchResult<N, C>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut distances = HashMap::from([(start.clone(), C::default())]);
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((C::default(), start.clone()))]);
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        if distances.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            if distances.get(&next).is_none_or(|&best| next_cost < best) {
+                distances.insert(next.clone(), next_cost);
+                predecessors.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    SearchResult {
+        start,
+        distances,
+        predecessors,
+    }
+}
+
+/// A* search from `start` to the first node that satisfies `is_goal`.
+/// Returns the path, including start and goal, and its cost.
+///
+/// `heuristic` must not overestimate the remaining cost, otherwise the path may not be the shortest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::default())]);
+    let mut predecessors: HashMap<N, N> = HashMap::new();
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if is_goal(&node) {
+            let mut path = vec![node];
+            while let Some(previous) = predecessors.get(path.last()?) {
+                path.push(previous.clone());
+            }
+            path.reverse();
+            return Some((path, cost));
+        }
+
+        if costs.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+            if costs.get(&next).is_none_or(|&best| next_cost < best) {
+                costs.insert(next.clone(), next_cost);
+                predecessors.insert(next.clone(), node.clone());
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+/// An error which is returned when a graph that should be acyclic contains a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N> {
+    /// A node that is part of the cycle.
+    pub node: N,
+}
+
+impl<N: Debug> Display for CycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle through {:?}", self.node)
+    }
+}
+
+impl<N: Debug> std::error::Error for CycleError<N> {}
+
+/// Sorts all nodes that are reachable from `starts` so that every node comes before its successors.
+pub fn toposort<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut finished: HashSet<N> = HashSet::new();
+    let mut in_progress: HashSet<N> = HashSet::new();
+    let mut order = Vec::new();
+
+    for start in starts {
+        if finished.contains(&start) {
+            continue;
+        }
+
+        // NOTE: an explicit stack of successor lists, so deep graphs don't overflow the call stack.
+        in_progress.insert(start.clone());
+        let mut stack = vec![(start.clone(), successors(&start).into_iter())];
+
+        while let Some((node, pending)) = stack.last_mut() {
+            match pending.next() {
+                Some(next) if in_progress.contains(&next) => {
+                    return Err(CycleError { node: next });
+                }
+                Some(next) if finished.contains(&next) => {}
+                Some(next) => {
+                    in_progress.insert(next.clone());
+                    let next_successors = successors(&next).into_iter();
+                    stack.push((next, next_successors));
+                }
+                None => {
+                    let node = node.clone();
+                    in_progress.remove(&node);
+                    finished.insert(node.clone());
+                    order.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// Counts the paths from `start` to nodes that satisfy `is_goal` in a directed acyclic graph.
+///
+/// Each node is visited once, so the count may be exponentially larger than the graph.
+/// A path may continue past a goal to reach further goals.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Result<u128, CycleError<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let order = toposort([start.clone()], &mut successors)?;
+    let mut counts: HashMap<N, u128> = HashMap::with_capacity(order.len());
+
+    for node in order.into_iter().rev() {
+        let own = u128::from(is_goal(&node));
+        let count = successors(&node)
+            .into_iter()
+            .fold(own, |count, next| count + counts[&next]);
+        counts.insert(node, count);
+    }
+
+    Ok(counts[&start])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CycleError, astar, bfs, count_paths, dijkstra, toposort};
+    use crate::{geometry::Point2, grid::Grid};
+
+    fn maze() -> Grid<char> {
+        ["S.#.....", ".##.###.", "....#...", "##.##.#E"]
+            .join("\n")
+            .parse()
+            .unwrap()
+    }
+
+    fn open_neighbors(grid: &Grid<char>, point: Point2) -> impl Iterator<Item = Point2> + '_ {
+        point
+            .neighbors4()
+            .filter(|&n| grid.get_point(n).is_some_and(|c| *c != '#'))
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let grid = maze();
+        let start = Point2::new(0, 0);
+        let end = Point2::new(7, 3);
+
+        let result = bfs(start, |&p| open_neighbors(&grid, p));
+        assert_eq!(result.distance(&end), Some(14));
+        assert_eq!(result.distance(&Point2::new(2, 0)), None);
+
+        let path = result.path_to(&end).unwrap();
+        assert_eq!(path.len(), 15);
+        assert_eq!(path[0], start);
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // 0 -> 1 is expensive directly, but cheap via 2.
+        let edges = |n: &u32| match n {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(1, 2), (3, 7)],
+            1 => vec![(3, 1)],
+            _ => vec![],
+        };
+
+        let result = dijkstra(0, edges);
+        assert_eq!(result.distance(&1), Some(3));
+        assert_eq!(result.distance(&3), Some(4));
+        assert_eq!(result.path_to(&3), Some(vec![0, 2, 1, 3]));
+
+        let (path, cost) = astar(0, edges, |_| 0, |n| *n == 3).unwrap();
+        assert_eq!(path, vec![0, 2, 1, 3]);
+        assert_eq!(cost, 4);
+    }
+
+    #[test]
+    fn searches_grids_with_heuristic() {
+        let grid = maze();
+        let end = Point2::new(7, 3);
+
+        let (path, cost) = astar(
+            Point2::new(0, 0),
+            |&p| open_neighbors(&grid, p).map(|n| (n, 1_u64)),
+            |p| p.manhattan(end),
+            |p| *p == end,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 14);
+        assert_eq!(path.last(), Some(&end));
+        assert!(astar(Point2::new(0, 0), |_| [], |_| 0_u64, |p| *p == end).is_none());
+    }
+
+    #[test]
+    fn sorts_topologically() {
+        let successors = |n: &u32| match n {
+            1 => vec![2, 3],
+            2 => vec![4],
+            3 => vec![4],
+            _ => vec![],
+        };
+
+        let order = toposort([1], successors).unwrap();
+        assert_eq!(order.len(), 4);
+        let index = |n: u32| order.iter().position(|&o| o == n).unwrap();
+        assert!(index(1) < index(2) && index(1) < index(3));
+        assert!(index(2) < index(4) && index(3) < index(4));
+
+        let cyclic = |n: &u32| vec![(n + 1) % 3];
+        assert!(matches!(toposort([0], cyclic), Err(CycleError { .. })));
+    }
+
+    #[test]
+    fn counts_paths() {
+        // a ladder of 100 diamonds has 2^100 paths.
+        let successors = |n: &u32| match n {
+            n if *n >= 200 => vec![],
+            n if n % 2 == 0 => vec![n + 1, n + 2],
+            n => vec![n + 1],
+        };
+
+        let count = count_paths(0, successors, |n| *n == 200).unwrap();
+        assert_eq!(count, 1 << 100);
+    }
+
+    #[test]
+    fn handles_deep_graphs() {
+        let successors = |n: &u32| if *n < 200_000 { vec![n + 1] } else { vec![] };
+        assert_eq!(toposort([0], successors).unwrap().len(), 200_001);
+        assert_eq!(count_paths(0, successors, |_| true), Ok(200_001));
+    }
+}
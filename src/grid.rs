@@ -0,0 +1,565 @@
+//! A two-dimensional grid of cells, as found in many puzzle inputs.
+//!
+//! Positions are given as `(row, col)`, with `(0, 0)` being the top left cell,
+//! or as a [`Point2`] with `x` being the column and `y` the row.
+
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::{Direction4, Direction8, Point2};
+
+/// A rectangular grid, stored as a flat vector in row-major order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError<E> {
+    /// A character could not be converted into a cell.
+    Cell { row: usize, col: usize, error: E },
+    /// A line has a different length than the first line.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl<E: fmt::Debug> Display for GridParseError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Cell { row, col, error } => {
+                write!(f, "invalid cell at row {row}, column {col}: {error:?}")
+            }
+            Self::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for GridParseError<E> {}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from cells in row-major order.
+    /// Returns [`None`] if the number of cells is not a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 {
+            return cells.is_empty().then_some(Self {
+                cells,
+                width: 0,
+                height: 0,
+            });
+        }
+
+        if !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        let height = cells.len() / width;
+        Some(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Parses a grid from lines of text, converting each character into a cell.
+    /// Cell types that implement `From<char>` can be parsed as well.
+    pub fn parse(input: &str) -> Result<Self, GridParseError<T::Error>>
+    where
+        T: TryFrom<char>,
+    {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+
+            for (col, c) in line.chars().enumerate() {
+                let cell =
+                    T::try_from(c).map_err(|error| GridParseError::Cell { row, col, error })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - before;
+            let expected = *width.get_or_insert(found);
+
+            if found != expected {
+                return Err(GridParseError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width: width.unwrap_or_default(),
+            height,
+        })
+    }
+
+    /// Number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Whether `(row, col)` is inside the grid.
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        row < self.height && col < self.width
+    }
+
+    fn index_of(&self, row: usize, col: usize) -> Option<usize> {
+        self.contains(row, col).then(|| row * self.width + col)
+    }
+
+    /// Returns the cell at `(row, col)`, or [`None`] if it's out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.index_of(row, col).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at `(row, col)` mutably, or [`None`] if it's out of bounds.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        self.index_of(row, col).map(|i| &mut self.cells[i])
+    }
+
+    /// Whether `point` is inside the grid, with `x` being the column and `y` the row.
+    pub fn contains_point(&self, point: Point2) -> bool {
+        point
+            .to_grid()
+            .is_some_and(|(row, col)| self.contains(row, col))
+    }
+
+    /// Returns the cell at `point`, or [`None`] if it's out of bounds.
+    pub fn get_point(&self, point: Point2) -> Option<&T> {
+        let (row, col) = point.to_grid()?;
+        self.get(row, col)
+    }
+
+    /// Returns the cell at `point` mutably, or [`None`] if it's out of bounds.
+    pub fn get_point_mut(&mut self, point: Point2) -> Option<&mut T> {
+        let (row, col) = point.to_grid()?;
+        self.get_mut(row, col)
+    }
+
+    /// Returns the cell at `(row, col)` offset by `(dr, dc)`, or [`None`] if that is out of bounds.
+    pub fn get_offset(&self, row: usize, col: usize, dr: isize, dc: isize) -> Option<&T> {
+        let (row, col) = self.offset(row, col, dr, dc)?;
+        self.get(row, col)
+    }
+
+    /// Returns the cell at `(row, col)`, wrapping around the edges in both directions.
+    ///
+    /// # Panics
+    /// Panics if the grid is empty.
+    pub fn get_wrapping(&self, row: isize, col: isize) -> &T {
+        assert!(!self.is_empty(), "cannot index into an empty grid");
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        let (row, col) = (
+            row.rem_euclid(self.height as isize) as usize,
+            col.rem_euclid(self.width as isize) as usize,
+        );
+        &self[(row, col)]
+    }
+
+    /// Position `(row, col)` offset by `(dr, dc)`, or [`None`] if that is out of bounds.
+    pub fn offset(&self, row: usize, col: usize, dr: isize, dc: isize) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(dr)?;
+        let col = col.checked_add_signed(dc)?;
+        self.contains(row, col).then_some((row, col))
+    }
+
+    /// Position `(row, col)` moved by one step in `direction`, or [`None`] if that is out of bounds.
+    pub fn step(
+        &self,
+        row: usize,
+        col: usize,
+        direction: impl Into<Point2>,
+    ) -> Option<(usize, usize)> {
+        let delta = direction.into();
+        let (dr, dc) = (
+            isize::try_from(delta.y).ok()?,
+            isize::try_from(delta.x).ok()?,
+        );
+        self.offset(row, col, dr, dc)
+    }
+
+    fn neighbors<D: Into<Point2>>(
+        &self,
+        row: usize,
+        col: usize,
+        directions: impl IntoIterator<Item = D>,
+    ) -> impl Iterator<Item = (usize, usize, &T)> {
+        directions.into_iter().filter_map(move |direction| {
+            let (row, col) = self.step(row, col, direction)?;
+            Some((row, col, &self[(row, col)]))
+        })
+    }
+
+    /// Iterates the orthogonal neighbors of `(row, col)` that are inside the grid.
+    pub fn neighbors4(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.neighbors(row, col, Direction4::ALL)
+    }
+
+    /// Iterates the orthogonal and diagonal neighbors of `(row, col)` that are inside the grid.
+    pub fn neighbors8(&self, row: usize, col: usize) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.neighbors(row, col, Direction8::ALL)
+    }
+
+    /// Iterates all cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| (i / width, i % width, cell))
+    }
+
+    /// Iterates all cells mutably with their positions, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, usize, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| (i / width, i % width, cell))
+    }
+
+    /// Iterates all positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// The cells of a row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Iterates the rows as slices.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// Iterates the cells of a column, top to bottom.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.ray(0, col, 1, 0)
+    }
+
+    /// Iterates the columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Iterates the cells starting at `(row, col)` in the direction `(dr, dc)` until the edge of the grid.
+    pub fn ray(&self, row: usize, col: usize, dr: isize, dc: isize) -> impl Iterator<Item = &T> {
+        let start = self.contains(row, col).then_some((row, col));
+        std::iter::successors(start, move |&(row, col)| self.offset(row, col, dr, dc))
+            .map(|(row, col)| &self[(row, col)])
+    }
+
+    /// Iterates the diagonal starting at `(row, col)` towards the bottom right.
+    pub fn diagonal(&self, row: usize, col: usize) -> impl Iterator<Item = &T> {
+        self.ray(row, col, 1, 1)
+    }
+
+    /// Iterates the anti-diagonal starting at `(row, col)` towards the bottom left.
+    pub fn anti_diagonal(&self, row: usize, col: usize) -> impl Iterator<Item = &T> {
+        self.ray(row, col, 1, -1)
+    }
+
+    /// Position of the first cell, row by row, that satisfies `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let i = self.cells.iter().position(predicate)?;
+        Some((i / self.width, i % self.width))
+    }
+
+    /// Position of the first cell, row by row, that equals `value`.
+    pub fn position(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// Iterates the positions of all cells that equal `value`.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = (usize, usize)> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, _, cell)| *cell == value)
+            .map(|(row, col, _)| (row, col))
+    }
+
+    /// Creates a grid of the same size by applying `f` to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Creates a grid of the given size, with cells taken from `(row, col)` of this grid as returned by `source`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[source(i / width, i % width)].clone())
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, turning rows into columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    #[must_use]
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(height, self.width, |row, col| (height - 1 - col, row))
+    }
+
+    /// Rotates the grid by 90° counterclockwise.
+    #[must_use]
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, width, |row, col| (col, width - 1 - row))
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = GridParseError<T::Error>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("position ({row}, {col}) is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("position ({row}, {col}) is out of bounds"))
+    }
+}
+
+impl<T> Index<Point2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point2) -> &Self::Output {
+        self.get_point(point)
+            .unwrap_or_else(|| panic!("point {point} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point2> for Grid<T> {
+    fn index_mut(&mut self, point: Point2) -> &mut Self::Output {
+        self.get_point_mut(point)
+            .unwrap_or_else(|| panic!("point {point} is out of bounds"))
+    }
+}
+
+/// Displays the grid with one line per row, each cell using its own `Display`.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridParseError};
+    use crate::geometry::{Direction4, Direction8, Point2};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grid() {
+        let grid = grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.get(2, 0), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        #[derive(Debug)]
+        struct Digit(u32);
+
+        impl TryFrom<char> for Digit {
+            type Error = char;
+
+            fn try_from(c: char) -> Result<Self, Self::Error> {
+                c.to_digit(10).map(Digit).ok_or(c)
+            }
+        }
+
+        assert_eq!(
+            Grid::<Digit>::parse("12\n3x").unwrap_err(),
+            GridParseError::Cell {
+                row: 1,
+                col: 1,
+                error: 'x'
+            }
+        );
+        assert_eq!(
+            Grid::<char>::parse("ab\nc").unwrap_err(),
+            GridParseError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn accesses_cells() {
+        let mut grid = grid();
+        assert_eq!(grid.get_offset(0, 0, 1, 1), Some(&'e'));
+        assert_eq!(grid.get_offset(0, 0, -1, 0), None);
+        assert_eq!(*grid.get_wrapping(-1, 4), 'e');
+        assert_eq!(grid[Point2::new(2, 1)], 'f');
+        assert_eq!(grid.get_point(Point2::new(-1, 0)), None);
+        assert_eq!(grid.step(1, 1, Direction4::Up), Some((0, 1)));
+        assert_eq!(grid.step(1, 1, Direction8::SE), None);
+        grid[(0, 0)] = 'z';
+        assert_eq!(grid.row(0), ['z', 'b', 'c']);
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = grid();
+        let n4: String = grid.neighbors4(0, 1).map(|(_, _, c)| c).collect();
+        assert_eq!(n4, "cea");
+        let n8: String = grid.neighbors8(0, 1).map(|(_, _, c)| c).collect();
+        assert_eq!(n8, "cfeda");
+    }
+
+    #[test]
+    fn views_lines() {
+        let grid: Grid<char> = "abc\ndef\nghi".parse().unwrap();
+        assert_eq!(grid.column(1).collect::<String>(), "beh");
+        assert_eq!(grid.diagonal(0, 0).collect::<String>(), "aei");
+        assert_eq!(grid.anti_diagonal(0, 2).collect::<String>(), "ceg");
+        assert_eq!(grid.ray(2, 2, 0, -1).collect::<String>(), "ihg");
+        assert_eq!(grid.columns().count(), 3);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "a.b\n.a.".parse().unwrap();
+        assert_eq!(grid.position(&'b'), Some((0, 2)));
+        assert_eq!(grid.find(|c| *c == 'x'), None);
+        assert_eq!(
+            grid.positions_of(&'a').collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn transforms_grid() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.map(|c| c.is_ascii_lowercase()).len(), 6);
+    }
+
+    #[test]
+    fn creates_grids() {
+        assert_eq!(Grid::new(2, 3, 0).len(), 6);
+        assert!(Grid::from_vec(2, vec![1, 2, 3]).is_none());
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3, 4]).unwrap().height(), 2);
+        assert!(Grid::<char>::parse("").unwrap().is_empty());
+    }
+}
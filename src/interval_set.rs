@@ -0,0 +1,291 @@
+//! A set of integers, stored as sorted, disjoint inclusive intervals.
+
+use std::ops::RangeInclusive;
+
+/// An integer type that can be used as the bound of an interval.
+pub trait Bound: Copy + Ord {
+    /// The next larger value, or [`None`] on overflow.
+    fn succ(self) -> Option<Self>;
+    /// The next smaller value, or [`None`] on overflow.
+    fn pred(self) -> Option<Self>;
+    /// Number of values in `low..=high`. Expects `low <= high`.
+    fn count(low: Self, high: Self) -> u128;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                #[allow(clippy::cast_sign_loss, clippy::cast_lossless)]
+                fn count(low: Self, high: Self) -> u128 {
+                    // NOTE: the difference of two 64-bit values always fits into 128 bits.
+                    (high as i128 - low as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_bound!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers that is stored as intervals, so large ranges take constant space.
+///
+/// Intervals that overlap or touch are coalesced on insertion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values of `range` to the set. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // first interval that overlaps or touches the new one.
+        let first = self
+            .intervals
+            .partition_point(|&(_, e)| e.succ().is_some_and(|after| after < start));
+
+        // first interval after that which is separated from the new one by a gap.
+        let last = first
+            + self.intervals[first..]
+                .partition_point(|&(s, _)| end.succ().is_none_or(|after| s <= after));
+
+        let merged = if first == last {
+            (start, end)
+        } else {
+            (
+                start.min(self.intervals[first].0),
+                end.max(self.intervals[last - 1].1),
+            )
+        };
+
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Whether `value` is in the set, found by binary search.
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.intervals.partition_point(|&(_, e)| e < value);
+        self.intervals.get(index).is_some_and(|&(s, _)| s <= value)
+    }
+
+    /// Number of values in the set. Can't overflow for any bound type.
+    pub fn len(&self) -> u128 {
+        self.intervals.iter().map(|&(s, e)| T::count(s, e)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of disjoint intervals the set is made of.
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// Iterates the disjoint intervals in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..=e)
+    }
+
+    /// Iterates the ranges between consecutive intervals, i.e. the values that are missing
+    /// between the smallest and the largest value of the set.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.windows(2).filter_map(|pair| {
+            let (_, e) = pair[0];
+            let (s, _) = pair[1];
+            // NOTE: intervals are separated by at least one value, so neither can overflow.
+            Some(e.succ()?..=s.pred()?)
+        })
+    }
+
+    /// Values that are in `self` or `other`.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for range in other.iter() {
+            result.insert(range);
+        }
+        result
+    }
+
+    /// Values that are in both `self` and `other`.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(&(s1, e1)), Some(&(s2, e2))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (s1.max(s2), e1.min(e2));
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if e1 < e2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// Values that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut j = 0;
+
+        for &(s, e) in &self.intervals {
+            let mut start = Some(s);
+
+            // skip intervals of `other` that end before this one.
+            while other.intervals.get(j).is_some_and(|&(_, oe)| oe < s) {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let (Some(current), Some(&(os, oe))) = (start, other.intervals.get(k)) {
+                if os > e {
+                    break;
+                }
+                if current < os {
+                    // NOTE: `os > current`, so it has a predecessor.
+                    intervals.push((current, os.pred().unwrap()));
+                }
+                start = oe.succ().filter(|&after| after <= e);
+                if oe > e {
+                    break;
+                }
+                k += 1;
+            }
+
+            if let Some(current) = start {
+                intervals.push((current, e));
+            }
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Bound> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Bound> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::IntervalSet;
+
+    fn set(ranges: &[(i64, i64)]) -> IntervalSet<i64> {
+        ranges.iter().map(|&(s, e)| s..=e).collect()
+    }
+
+    fn ranges(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.iter().map(|r| r.into_inner()).collect()
+    }
+
+    #[test]
+    fn coalesces_on_insert() {
+        let s = set(&[(3, 5), (10, 14), (16, 20), (12, 18)]);
+        assert_eq!(ranges(&s), vec![(3, 5), (10, 20)]);
+
+        let s = set(&[(1, 2), (3, 4), (8, 9), (6, 6)]);
+        assert_eq!(ranges(&s), vec![(1, 4), (6, 6), (8, 9)]);
+
+        let s = set(&[(5, 1), (0, 0)]);
+        assert_eq!(ranges(&s), vec![(0, 0)]);
+    }
+
+    #[test]
+    fn checks_membership() {
+        let s = set(&[(3, 5), (10, 20)]);
+        assert!(s.contains(3));
+        assert!(s.contains(20));
+        assert!(!s.contains(2));
+        assert!(!s.contains(7));
+        assert!(!s.contains(21));
+    }
+
+    #[test]
+    fn counts_values() {
+        assert_eq!(set(&[(3, 5), (10, 20)]).len(), 14);
+        assert!(set(&[]).is_empty());
+
+        let full: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(full.len(), u128::from(u64::MAX) + 1);
+
+        let extremes: IntervalSet<i64> = [i64::MIN..=i64::MAX].into_iter().collect();
+        assert_eq!(extremes.len(), 1 << 64);
+    }
+
+    #[test]
+    fn iterates_gaps() {
+        let s = set(&[(1, 2), (5, 5), (7, 9)]);
+        let gaps: Vec<_> = s.gaps().map(|r| r.into_inner()).collect();
+        assert_eq!(gaps, vec![(3, 4), (6, 6)]);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[(0, 10), (20, 30)]);
+        let b = set(&[(5, 25), (28, 40)]);
+
+        assert_eq!(ranges(&a.union(&b)), vec![(0, 40)]);
+        assert_eq!(
+            ranges(&a.intersection(&b)),
+            vec![(5, 10), (20, 25), (28, 30)]
+        );
+        assert_eq!(ranges(&a.difference(&b)), vec![(0, 4), (26, 27)]);
+        assert_eq!(ranges(&b.difference(&a)), vec![(11, 19), (31, 40)]);
+    }
+
+    #[test]
+    fn subtracts_at_bounds() {
+        let a: IntervalSet<u8> = [0..=255].into_iter().collect();
+        let b: IntervalSet<u8> = [0..=0, 10..=20, 255..=255].into_iter().collect();
+        let diff: Vec<_> = a.difference(&b).iter().map(|r| r.into_inner()).collect();
+        assert_eq!(diff, vec![(1, 9), (21, 254)]);
+        assert!(b.difference(&a).is_empty());
+    }
+}
@@ -0,0 +1,312 @@
+//! A k-d tree for nearest neighbor queries on [`Point2`] and [`Point3`].
+//!
+//! Besides k-nearest queries, it yields all pairs of points in increasing order of distance lazily,
+//! so algorithms that only need the closest pairs don't have to compute all `n²` distances.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::geometry::{Point2, Point3};
+
+/// A point that can be stored in a [`KdTree`].
+pub trait KdPoint: Copy {
+    const DIMENSIONS: usize;
+
+    /// Coordinate along `axis`, which is smaller than [`Self::DIMENSIONS`].
+    fn coordinate(self, axis: usize) -> i64;
+
+    fn distance_squared(self, other: Self) -> u128;
+}
+
+impl KdPoint for Point2 {
+    const DIMENSIONS: usize = 2;
+
+    fn coordinate(self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            _ => self.y,
+        }
+    }
+
+    fn distance_squared(self, other: Self) -> u128 {
+        Point2::distance_squared(self, other)
+    }
+}
+
+impl KdPoint for Point3 {
+    const DIMENSIONS: usize = 3;
+
+    fn coordinate(self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    fn distance_squared(self, other: Self) -> u128 {
+        Point3::distance_squared(self, other)
+    }
+}
+
+/// A balanced k-d tree over a fixed set of points. Points are referred to by their index in the input.
+///
+/// Distances are squared euclidean distances, and ties are broken by the smaller index,
+/// so all results are deterministic.
+#[derive(Debug, Clone)]
+pub struct KdTree<P> {
+    points: Vec<P>,
+    /// Point indices in tree order: the root of the subtree `nodes[lo..hi]` is at `(lo + hi) / 2`.
+    nodes: Vec<usize>,
+}
+
+impl<P: KdPoint> KdTree<P> {
+    pub fn new(points: Vec<P>) -> Self {
+        let mut nodes: Vec<usize> = (0..points.len()).collect();
+        build(&points, &mut nodes, 0);
+        Self { points, nodes }
+    }
+
+    pub fn points(&self) -> &[P] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The `k` points nearest to `query` as `(distance, index)`, ordered by distance and index.
+    /// The point at index `exclude` is skipped, to find the neighbors of a point of the tree.
+    pub fn nearest(&self, query: P, k: usize, exclude: Option<usize>) -> Vec<(u128, usize)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        self.search(query, k, exclude, 0..self.nodes.len(), 0, &mut best);
+        best.into_sorted_vec()
+    }
+
+    fn search(
+        &self,
+        query: P,
+        k: usize,
+        exclude: Option<usize>,
+        nodes: std::ops::Range<usize>,
+        depth: usize,
+        best: &mut BinaryHeap<(u128, usize)>,
+    ) {
+        if nodes.is_empty() || k == 0 {
+            return;
+        }
+
+        let mid = (nodes.start + nodes.end) / 2;
+        let index = self.nodes[mid];
+        let point = self.points[index];
+
+        if exclude != Some(index) {
+            let candidate = (query.distance_squared(point), index);
+            if best.len() < k {
+                best.push(candidate);
+            } else if best.peek().is_some_and(|&worst| candidate < worst) {
+                best.pop();
+                best.push(candidate);
+            }
+        }
+
+        let axis = depth % P::DIMENSIONS;
+        let (q, p) = (query.coordinate(axis), point.coordinate(axis));
+        let (near, far) = if q < p {
+            (nodes.start..mid, mid + 1..nodes.end)
+        } else {
+            (mid + 1..nodes.end, nodes.start..mid)
+        };
+
+        self.search(query, k, exclude, near, depth + 1, best);
+
+        // NOTE: points on the far side are at least as far away as the splitting plane.
+        // equal distances are still searched, as they may have a smaller index.
+        let plane = u128::from(q.abs_diff(p)).pow(2);
+        if best.len() < k || best.peek().is_some_and(|&(worst, _)| plane <= worst) {
+            self.search(query, k, exclude, far, depth + 1, best);
+        }
+    }
+
+    /// Iterates all pairs of points `(i, j, distance)` with `i < j`, ordered by distance, `i` and `j`.
+    ///
+    /// The neighbors of each point are fetched in batches of growing size as the iteration advances,
+    /// so taking the first `m` pairs is much cheaper than sorting all of them.
+    pub fn edges_by_distance(&self) -> EdgesByDistance<'_, P> {
+        let mut edges = EdgesByDistance {
+            tree: self,
+            neighbors: vec![Vec::new(); self.len()],
+            next: vec![0; self.len()],
+            heap: BinaryHeap::with_capacity(self.len()),
+        };
+
+        for point in 0..self.len() {
+            let k = INITIAL_NEIGHBORS.min(self.len() - 1);
+            edges.neighbors[point] = self.nearest(self.points[point], k, Some(point));
+            edges.push(point);
+        }
+
+        edges
+    }
+}
+
+fn build<P: KdPoint>(points: &[P], nodes: &mut [usize], depth: usize) {
+    if nodes.len() <= 1 {
+        return;
+    }
+
+    let axis = depth % P::DIMENSIONS;
+    let mid = nodes.len() / 2;
+    nodes.select_nth_unstable_by_key(mid, |&i| points[i].coordinate(axis));
+
+    let (left, right) = nodes.split_at_mut(mid);
+    build(points, left, depth + 1);
+    build(points, &mut right[1..], depth + 1);
+}
+
+/// Number of neighbors that are fetched for each point before the first pair is returned.
+const INITIAL_NEIGHBORS: usize = 8;
+
+/// Iterator over the pairs of points of a [`KdTree`], see [`KdTree::edges_by_distance`].
+#[derive(Debug, Clone)]
+pub struct EdgesByDistance<'a, P> {
+    tree: &'a KdTree<P>,
+    /// The nearest neighbors of each point that were fetched so far.
+    neighbors: Vec<Vec<(u128, usize)>>,
+    /// Position of the next neighbor of each point.
+    next: Vec<usize>,
+    /// The next neighbor of every point as `(distance, low index, high index, point)`.
+    heap: BinaryHeap<Reverse<(u128, usize, usize, usize)>>,
+}
+
+impl<P: KdPoint> EdgesByDistance<'_, P> {
+    /// Pushes the next neighbor of `point` onto the heap, fetching more neighbors if needed.
+    fn push(&mut self, point: usize) {
+        let position = self.next[point];
+        let fetched = self.neighbors[point].len();
+
+        if position == fetched && fetched < self.tree.len() - 1 {
+            let k = (fetched * 2).min(self.tree.len() - 1);
+            self.neighbors[point] = self.tree.nearest(self.tree.points[point], k, Some(point));
+        }
+
+        if let Some(&(distance, other)) = self.neighbors[point].get(position) {
+            let (low, high) = (point.min(other), point.max(other));
+            self.heap.push(Reverse((distance, low, high, point)));
+        }
+    }
+}
+
+impl<P: KdPoint> Iterator for EdgesByDistance<'_, P> {
+    type Item = (usize, usize, u128);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Reverse((distance, low, high, point)) = self.heap.pop()?;
+
+            self.next[point] += 1;
+            self.push(point);
+
+            // NOTE: every pair is found from both of its points, only the lower one returns it.
+            if point == low {
+                return Some((low, high, distance));
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::KdTree;
+    use crate::geometry::{Point2, Point3};
+    use crate::rng::Rng;
+
+    fn random_points(rng: &mut Rng, n: usize, range: i64) -> Vec<Point3> {
+        (0..n)
+            .map(|_| {
+                Point3::new(
+                    rng.range(-range..=range),
+                    rng.range(-range..=range),
+                    rng.range(-range..=range),
+                )
+            })
+            .collect()
+    }
+
+    fn all_edges(points: &[Point3]) -> Vec<(usize, usize, u128)> {
+        let mut edges = vec![];
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                edges.push((i, j, points[i].distance_squared(points[j])));
+            }
+        }
+        edges.sort_unstable_by_key(|&(i, j, d)| (d, i, j));
+        edges
+    }
+
+    #[test]
+    fn finds_nearest_points() {
+        let mut rng = Rng::new(8);
+
+        for _ in 0..50 {
+            let n = 1 + rng.index(60);
+            let points = random_points(&mut rng, n, 5);
+            let tree = KdTree::new(points.clone());
+            let query = random_points(&mut rng, 1, 6)[0];
+            let k = rng.index(points.len() + 2);
+
+            let mut expected: Vec<_> = points
+                .iter()
+                .enumerate()
+                .map(|(i, p)| (query.distance_squared(*p), i))
+                .collect();
+            expected.sort_unstable();
+            expected.truncate(k);
+
+            assert_eq!(tree.nearest(query, k, None), expected);
+        }
+    }
+
+    #[test]
+    fn excludes_the_query_point() {
+        let points = vec![Point2::new(0, 0), Point2::new(3, 4), Point2::new(1, 1)];
+        let tree = KdTree::new(points);
+        assert_eq!(
+            tree.nearest(Point2::new(0, 0), 2, Some(0)),
+            vec![(2, 2), (25, 1)]
+        );
+    }
+
+    #[test]
+    fn yields_edges_by_distance() {
+        let mut rng = Rng::new(9);
+
+        for _ in 0..50 {
+            // a small range produces many equal distances.
+            let n = rng.index(40);
+            let points = random_points(&mut rng, n, 3);
+            let tree = KdTree::new(points.clone());
+            assert_eq!(
+                tree.edges_by_distance().collect::<Vec<_>>(),
+                all_edges(&points)
+            );
+        }
+    }
+
+    #[test]
+    fn yields_closest_edges_first() {
+        let mut rng = Rng::new(10);
+        let points = random_points(&mut rng, 800, 100_000);
+        let tree = KdTree::new(points.clone());
+
+        let expected: Vec<_> = all_edges(&points).into_iter().take(500).collect();
+        let edges: Vec<_> = tree.edges_by_distance().take(500).collect();
+        assert_eq!(edges, expected);
+    }
+}
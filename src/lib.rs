@@ -1,3 +1,14 @@
+pub mod checked;
+pub mod disjoint_set;
+pub mod fixed_width;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod interval_set;
+pub mod kd_tree;
+pub mod math;
+pub mod parse;
+pub mod rng;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
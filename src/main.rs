@@ -1,4 +1,4 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{all, download, fuzz, read, scaffold, solve, time};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,8 +7,8 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{Day, export::ExportFormat};
+    use std::{ffi::OsString, process};
 
     pub enum AppArguments {
         Download {
@@ -26,22 +26,60 @@ mod args {
             day: Day,
             release: bool,
             dhat: bool,
+            checked: bool,
             submit: Option<u8>,
         },
         All {
             release: bool,
         },
+        Fuzz {
+            day: Day,
+            iterations: Option<usize>,
+            size: Option<usize>,
+            seed: Option<u64>,
+        },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            dhat: bool,
+            scale: bool,
+            export: Option<(ExportFormat, String)>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Removes `--export FORMAT PATH` from the raw arguments, as pico-args only supports options with a single value.
+    fn take_export(
+        args: &mut Vec<OsString>,
+    ) -> Result<Option<(ExportFormat, String)>, Box<dyn std::error::Error>> {
+        let Some(pos) = args.iter().position(|arg| arg == "--export") else {
+            return Ok(None);
+        };
+
+        let values: Vec<OsString> = args.drain(pos..args.len().min(pos + 3)).collect();
+
+        match values.as_slice() {
+            [_, format, path]
+                if ![format, path]
+                    .iter()
+                    .any(|value| value.to_string_lossy().starts_with("--")) =>
+            {
+                let format = format.to_str().ok_or("invalid export format")?.parse()?;
+                let path = path.to_str().ok_or("invalid export path")?.to_string();
+                Ok(Some((format, path)))
+            }
+            _ => Err(
+                "`--export` expects a format and a path, e.g. `--export csv timings.csv`".into(),
+            ),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut raw_args: Vec<OsString> = std::env::args_os().skip(1).collect();
+        let mut export = take_export(&mut raw_args)?;
+        let mut args = pico_args::Arguments::from_vec(raw_args);
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
@@ -50,13 +88,40 @@ mod args {
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let dhat = args.contains("--dhat");
+                let scale = args.contains("--scale");
+
+                // NOTE: dhat slows down every allocation, those times must not end up in the readme.
+                if store && dhat {
+                    return Err("`--store` can't be combined with `--dhat`.".into());
+                }
+
+                // NOTE: scaling runs generated inputs of growing size, not the day's input.
+                let conflicts = [
+                    ("--all", all),
+                    ("--store", store),
+                    ("--dhat", dhat),
+                    ("--export", export.is_some()),
+                ];
+                if let Some((flag, _)) = conflicts.iter().find(|(_, set)| scale && *set) {
+                    return Err(format!("`--scale` can't be combined with `{flag}`.").into());
+                }
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    dhat,
+                    scale,
+                    export: export.take(),
                 }
             }
+            Some("fuzz") => AppArguments::Fuzz {
+                day: args.free_from_str()?,
+                iterations: args.opt_value_from_str("--iterations")?,
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
@@ -68,12 +133,23 @@ mod args {
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let dhat = args.contains("--dhat");
+                let checked = args.contains("--checked");
+
+                // NOTE: both select a cargo profile, only one of them can be used.
+                if dhat && checked {
+                    return Err("`--dhat` can't be combined with `--checked`.".into());
+                }
+
+                AppArguments::Solve {
+                    day: args.free_from_str()?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat,
+                    checked,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,6 +167,10 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
+        if export.is_some() {
+            eprintln!("Warning: `--export` is only supported by the `time` command.");
+        }
+
         Ok(app_args)
     }
 }
@@ -103,7 +183,27 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                dhat,
+                scale,
+                export,
+            } => match (scale, day) {
+                (true, Some(day)) => time::handle_scale(day),
+                (true, None) => {
+                    eprintln!("`--scale` needs a day, e.g. `cargo time 1 --scale`.");
+                    std::process::exit(1);
+                }
+                (false, _) => time::handle(day, all, store, dhat, export),
+            },
+            AppArguments::Fuzz {
+                day,
+                iterations,
+                size,
+                seed,
+            } => fuzz::handle(day, iterations, size, seed),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold {
@@ -120,8 +220,9 @@ fn main() {
                 day,
                 release,
                 dhat,
+                checked,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => solve::handle(day, release, dhat, checked, submit),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
@@ -0,0 +1,332 @@
+//! Digit manipulation and number theory.
+//!
+//! Digit functions work on the decimal representation and never allocate.
+//! Functions that can overflow return [`None`] instead.
+
+/* --------------------------------- digits --------------------------------- */
+
+/// Value of an ASCII digit, e.g. `7` for `b'7'`.
+pub fn digit(byte: u8) -> Option<u8> {
+    byte.is_ascii_digit().then(|| byte - b'0')
+}
+
+/// `10^exp`, or [`None`] if it does not fit into a `u64`.
+pub fn pow10(exp: u32) -> Option<u64> {
+    10_u64.checked_pow(exp)
+}
+
+/// Number of decimal digits of `n`. `0` has one digit.
+pub fn digit_count(n: u64) -> u32 {
+    n.checked_ilog10().unwrap_or(0) + 1
+}
+
+/// Iterates the digits of `n`, starting with the most significant one.
+pub fn digits(n: u64) -> impl Iterator<Item = u8> {
+    let mut divisor = Some(10_u64.pow(digit_count(n) - 1));
+
+    std::iter::from_fn(move || {
+        let d = divisor?;
+        divisor = (d > 1).then_some(d / 10);
+        #[allow(clippy::cast_possible_truncation)]
+        Some((n / d % 10) as u8)
+    })
+}
+
+/// Iterates the digits of `n`, starting with the least significant one.
+pub fn digits_rev(n: u64) -> impl Iterator<Item = u8> {
+    let mut rest = Some(n);
+
+    std::iter::from_fn(move || {
+        let r = rest?;
+        rest = (r >= 10).then_some(r / 10);
+        #[allow(clippy::cast_possible_truncation)]
+        Some((r % 10) as u8)
+    })
+}
+
+/// Builds a number from its digits, most significant first.
+/// Returns [`None`] if a digit is larger than `9` or the number overflows.
+pub fn from_digits(digits: impl IntoIterator<Item = u8>) -> Option<u64> {
+    digits.into_iter().try_fold(0_u64, |n, d| {
+        if d > 9 {
+            return None;
+        }
+        n.checked_mul(10)?.checked_add(u64::from(d))
+    })
+}
+
+/// Writes the digits of `b` after the digits of `a`, e.g. `12 || 345 = 12345`.
+pub fn concat(a: u64, b: u64) -> Option<u64> {
+    a.checked_mul(pow10(digit_count(b))?)?.checked_add(b)
+}
+
+/// Splits off the last `k` digits of `n`, e.g. `(123, 45)` for `12345` and `k = 2`.
+pub fn split_digits(n: u64, k: u32) -> (u64, u64) {
+    match pow10(k) {
+        Some(divisor) => (n / divisor, n % divisor),
+        None => (0, n),
+    }
+}
+
+/// The number made of `len` ones, e.g. `1111` for `len = 4`.
+pub fn repunit(len: u32) -> Option<u64> {
+    repeat_multiplier(1, len)
+}
+
+/// The number that repeats a block of `block_len` digits `times` times when multiplied with it,
+/// e.g. `10101` for `block_len = 2` and `times = 3`, as `10101 * 47 = 474747`.
+pub fn repeat_multiplier(block_len: u32, times: u32) -> Option<u64> {
+    let shift = pow10(block_len)?;
+    (0..times).try_fold(0_u64, |m, _| m.checked_mul(shift)?.checked_add(1))
+}
+
+/// Writes the digits of `pattern` `times` times in a row, e.g. `474747` for `47` and `3`.
+pub fn repeat(pattern: u64, times: u32) -> Option<u64> {
+    pattern.checked_mul(repeat_multiplier(digit_count(pattern), times)?)
+}
+
+/// Whether the digits of `n` are a block of digits repeated exactly `times` times, e.g. `474747` for `3`.
+pub fn is_repetition(n: u64, times: u32) -> bool {
+    let len = digit_count(n);
+    if times == 0 || !len.is_multiple_of(times) {
+        return false;
+    }
+
+    // NOTE: a multiple of the multiplier with `len` digits always has a block of `len / times` digits.
+    repeat_multiplier(len / times, times).is_some_and(|m| n.is_multiple_of(m))
+}
+
+/* ------------------------------ number theory ----------------------------- */
+
+/// Greatest common divisor. `gcd(0, 0)` is `0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or [`None`] on overflow. `lcm(0, n)` is `0`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of all `values`, or [`None`] on overflow. `1` if there are no values.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m` in `0..m`, or [`None`] if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `base^exp mod m` by repeated squaring.
+///
+/// # Panics
+/// Panics if `m` is `0`.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = u128::from(m);
+    let mut base = u128::from(base) % m;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+
+    // NOTE: the result is smaller than `m`, which came from a `u64`.
+    #[allow(clippy::cast_possible_truncation)]
+    let result = result as u64;
+    result
+}
+
+/// Solves the system `x = r (mod m)` for all `(r, m)` with the chinese remainder theorem.
+/// The moduli don't have to be coprime.
+///
+/// Returns `(x, lcm)` with the smallest non-negative solution `x` and the lcm of the moduli,
+/// or [`None`] if there is no solution or the lcm overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0_i64, 1_i64), |(x, m), (r, n)| {
+            if n <= 0 {
+                return None;
+            }
+
+            let r = r.rem_euclid(n);
+            let (g, p, _) = extended_gcd(m, n);
+            if (r - x) % g != 0 {
+                return None;
+            }
+
+            let lcm = m.checked_mul(n / g)?;
+            // NOTE: intermediate products can exceed 64 bits even if the result does not.
+            let step = i128::from((r - x) / g) * i128::from(p) % i128::from(n / g);
+            let x = (i128::from(x) + i128::from(m) * step).rem_euclid(i128::from(lcm));
+
+            Some((i64::try_from(x).ok()?, lcm))
+        })
+}
+
+/// The smallest integer whose square is at least `n`.
+pub fn isqrt_ceil(n: u64) -> u64 {
+    let root = n.isqrt();
+    if root * root == n { root } else { root + 1 }
+}
+
+/// The square root of `n` if `n` is a perfect square.
+pub fn exact_sqrt(n: u64) -> Option<u64> {
+    let root = n.isqrt();
+    (root * root == n).then_some(root)
+}
+
+/// The Möbius function: `0` if `n` has a squared prime factor,
+/// otherwise `1` or `-1` for an even or odd number of prime factors. `mobius(1)` is `1`.
+///
+/// # Panics
+/// Panics if `n` is `0`.
+pub fn mobius(mut n: u64) -> i64 {
+    assert!(n > 0, "mobius(0) is undefined");
+    let mut sign = 1;
+    let mut p = 2;
+
+    while p <= n / p {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            sign = -sign;
+        }
+        p += 1;
+    }
+
+    if n > 1 { -sign } else { sign }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterates_digits() {
+        assert_eq!(digits(9075).collect::<Vec<_>>(), vec![9, 0, 7, 5]);
+        assert_eq!(digits_rev(9075).collect::<Vec<_>>(), vec![5, 7, 0, 9]);
+        assert_eq!(digits(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(digits(u64::MAX).count(), 20);
+
+        assert_eq!(digit_count(0), 1);
+        assert_eq!(digit_count(999), 3);
+        assert_eq!(digit_count(1000), 4);
+        assert_eq!(digit(b'7'), Some(7));
+        assert_eq!(digit(b'x'), None);
+    }
+
+    #[test]
+    fn builds_numbers() {
+        assert_eq!(from_digits([9, 0, 7, 5]), Some(9075));
+        assert_eq!(from_digits(digits(u64::MAX)), Some(u64::MAX));
+        assert_eq!(from_digits([10]), None);
+        assert_eq!(from_digits([9; 21]), None);
+
+        assert_eq!(concat(12, 345), Some(12345));
+        assert_eq!(concat(12, 0), Some(120));
+        assert_eq!(concat(u64::MAX, 1), None);
+        assert_eq!(split_digits(12345, 2), (123, 45));
+        assert_eq!(split_digits(12345, 30), (0, 12345));
+    }
+
+    #[test]
+    fn repeats_patterns() {
+        assert_eq!(repunit(4), Some(1111));
+        assert_eq!(repeat_multiplier(2, 3), Some(10101));
+        assert_eq!(repeat(47, 3), Some(474747));
+        assert_eq!(repeat(1, 21), None);
+
+        assert!(is_repetition(474747, 3));
+        assert!(is_repetition(1188511885, 2));
+        assert!(!is_repetition(474747, 2));
+        assert!(!is_repetition(101, 3));
+        assert!(is_repetition(7, 1));
+    }
+
+    #[test]
+    fn computes_divisors() {
+        assert_eq!(gcd(48, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+        assert_eq!(extended_gcd(-4, 6).0, 2);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(2, 4), None);
+
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn computes_square_roots() {
+        assert_eq!(exact_sqrt(144), Some(12));
+        assert_eq!(exact_sqrt(145), None);
+        assert_eq!(isqrt_ceil(145), 13);
+        assert_eq!(isqrt_ceil(144), 12);
+        assert_eq!(exact_sqrt(u64::MAX), None);
+    }
+
+    #[test]
+    fn computes_mobius() {
+        let values: Vec<_> = (1..=12).map(mobius).collect();
+        assert_eq!(values, vec![1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0]);
+        assert_eq!(mobius(30), -1);
+        assert_eq!(mobius(u64::MAX), -1);
+    }
+}
@@ -0,0 +1,381 @@
+//! Helpers for parsing puzzle inputs that report errors with line numbers instead of panicking.
+//!
+//! None of the helpers allocate unless an error occurs or a collection is returned.
+
+use std::fmt::{self, Display};
+use std::iter::Peekable;
+use std::str::{FromStr, Split};
+
+/// An error in a line of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting at `1`.
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Display) -> Self {
+        Self {
+            line,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Iterates all integers in `s`, e.g. `-3` and `14` in `x=-3, y=14`.
+///
+/// A `-` directly after a digit is treated as a separator, so `1-5` yields `1` and `5`.
+///
+/// # Panics
+/// Panics with the offending integer if it doesn't fit into `T`, instead of dropping it from the row.
+pub fn ints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let bytes = s.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            let negative = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+
+            if negative || bytes[i].is_ascii_digit() {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let token = &s[start..i];
+                return Some(
+                    token
+                        .parse()
+                        .unwrap_or_else(|e| panic!("invalid integer `{token}`: {e}")),
+                );
+            } else {
+                i += 1;
+            }
+        }
+        None
+    })
+}
+
+/// Iterates the sections of an input that are separated by blank lines.
+/// Lines that only contain whitespace count as blank, and leading or trailing blank lines are skipped.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut rest = input;
+
+    std::iter::from_fn(move || {
+        // skip blank lines before the section.
+        while let Some((line, tail)) = split_line(rest) {
+            if !line.trim().is_empty() {
+                break;
+            }
+            rest = tail;
+        }
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        let start = rest;
+        let mut len = 0;
+
+        while let Some((line, tail)) = split_line(rest) {
+            if line.trim().is_empty() {
+                break;
+            }
+            len = start.len() - tail.len();
+            rest = tail;
+        }
+
+        Some(start[..len].trim_end_matches(['\n', '\r']))
+    })
+}
+
+/// Splits the first line off `s`, keeping the line break with the line.
+fn split_line(s: &str) -> Option<(&str, &str)> {
+    if s.is_empty() {
+        return None;
+    }
+    Some(match s.find('\n') {
+        Some(i) => s.split_at(i + 1),
+        None => (s, ""),
+    })
+}
+
+/// Parses each line with `FromStr`. Errors carry the line number.
+pub fn lines<T>(input: &str) -> impl Iterator<Item = Result<T, ParseError>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines_with(input, str::parse)
+}
+
+/// Parses each line with `f`. Errors carry the line number.
+pub fn lines_with<'a, T, E: Display>(
+    input: &'a str,
+    mut f: impl FnMut(&'a str) -> Result<T, E>,
+) -> impl Iterator<Item = Result<T, ParseError>> {
+    input
+        .lines()
+        .enumerate()
+        .map(move |(i, line)| f(line).map_err(|e| ParseError::new(i + 1, e)))
+}
+
+/// Parses a table of whitespace-separated values into its columns.
+///
+/// ```ignore
+/// let columns: Vec<Vec<u32>> = columns("3   4\n4   3\n2   5")?;
+/// assert_eq!(columns, vec![vec![3, 4, 2], vec![4, 3, 5]]);
+/// ```
+pub fn columns<T>(input: &str) -> Result<Vec<Vec<T>>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let mut columns: Vec<Vec<T>> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let line_number = i + 1;
+        let mut width = 0;
+
+        for (col, field) in line.split_whitespace().enumerate() {
+            let value = field
+                .parse()
+                .map_err(|e| ParseError::new(line_number, format!("column {}: {e}", col + 1)))?;
+
+            if i == 0 {
+                columns.push(Vec::new());
+            } else if col >= columns.len() {
+                return Err(ParseError::new(
+                    line_number,
+                    format!("expected {} columns, found more", columns.len()),
+                ));
+            }
+
+            columns[col].push(value);
+
+            width += 1;
+        }
+
+        if width != columns.len() {
+            return Err(ParseError::new(
+                line_number,
+                format!("expected {} columns, found {width}", columns.len()),
+            ));
+        }
+    }
+
+    Ok(columns)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which is returned by [`scan!`](crate::scan) if the input does not match the pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError(String);
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// Extracts the fields of a pattern like `{},{}` from an input, one at a time. Used by [`scan!`](crate::scan).
+pub struct Scanner<'p, 'i> {
+    pattern: &'p str,
+    input: &'i str,
+    literals: Peekable<Split<'p, &'static str>>,
+    rest: &'i str,
+}
+
+impl<'p, 'i> Scanner<'p, 'i> {
+    pub fn new(pattern: &'p str, input: &'i str) -> Result<Self, ScanError> {
+        let mut literals = pattern.split("{}").peekable();
+        let prefix = literals.next().unwrap_or_default();
+
+        let mut scanner = Self {
+            pattern,
+            input,
+            literals,
+            rest: input,
+        };
+
+        scanner.rest = input
+            .strip_prefix(prefix)
+            .ok_or_else(|| scanner.mismatch())?;
+
+        Ok(scanner)
+    }
+
+    fn mismatch(&self) -> ScanError {
+        ScanError(format!(
+            "`{}` does not match pattern `{}`",
+            self.input, self.pattern
+        ))
+    }
+
+    /// Extracts the next field and parses it.
+    pub fn field<T>(&mut self) -> Result<T, ScanError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let literal = self
+            .literals
+            .next()
+            .ok_or_else(|| ScanError(format!("pattern `{}` has too few fields", self.pattern)))?;
+
+        let is_last = self.literals.peek().is_none();
+
+        let (field, rest) = if is_last {
+            self.rest
+                .strip_suffix(literal)
+                .map(|field| (field, ""))
+                .ok_or_else(|| self.mismatch())?
+        } else if literal.is_empty() {
+            return Err(ScanError(format!(
+                "pattern `{}` has adjacent fields",
+                self.pattern
+            )));
+        } else {
+            self.rest
+                .split_once(literal)
+                .ok_or_else(|| self.mismatch())?
+        };
+
+        self.rest = rest;
+
+        field
+            .parse()
+            .map_err(|e| ScanError(format!("invalid field `{field}` in `{}`: {e}", self.input)))
+    }
+
+    /// Checks that the whole pattern was used.
+    pub fn finish(mut self) -> Result<(), ScanError> {
+        match self.literals.next() {
+            Some(_) => Err(ScanError(format!(
+                "pattern `{}` has more fields than types",
+                self.pattern
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Extracts typed fields from a string according to a pattern, where each `{}` is a field.
+/// Returns a tuple of the fields, or a [`ScanError`](crate::parse::ScanError).
+///
+/// ```ignore
+/// let (from, to) = advent_of_code::scan!("11-22", "{}-{}", u64, u64)?;
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:expr, $($t:ty),+ $(,)?) => {
+        (|| -> ::std::result::Result<($($t,)+), $crate::parse::ScanError> {
+            let mut scanner = $crate::parse::Scanner::new($pattern, $input)?;
+            let fields = ($(scanner.field::<$t>()?,)+);
+            scanner.finish()?;
+            Ok(fields)
+        })()
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ParseError, columns, ints, lines, lines_with, sections};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            ints::<i64>("x=-3, y=14 and 7-9").collect::<Vec<_>>(),
+            vec![-3, 14, 7, 9]
+        );
+        assert_eq!(ints::<u8>("1 255 4").collect::<Vec<_>>(), vec![1, 255, 4]);
+        assert_eq!(ints::<i32>("--5 - 6-").collect::<Vec<_>>(), vec![-5, 6]);
+        assert_eq!(ints::<i32>("no numbers").count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid integer `300`")]
+    fn rejects_integers_that_overflow() {
+        ints::<u8>("1 300 4").for_each(drop);
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\na\nb\n\n\nc\n \nd\r\n\r\n";
+        assert_eq!(sections(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+        assert_eq!(sections("").count(), 0);
+    }
+
+    #[test]
+    fn parses_lines() {
+        let parsed: Result<Vec<u32>, _> = lines("1\n2\n3").collect();
+        assert_eq!(parsed, Ok(vec![1, 2, 3]));
+
+        let parsed: Result<Vec<u32>, _> = lines("1\nx\n3").collect();
+        assert_eq!(parsed.unwrap_err().line, 2);
+
+        let parsed: Result<Vec<_>, _> =
+            lines_with("a-1\nb+2", |line| crate::scan!(line, "{}-{}", char, u8)).collect();
+        let error = parsed.unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.to_string(),
+            "line 2: `b+2` does not match pattern `{}-{}`"
+        );
+    }
+
+    #[test]
+    fn scans_patterns() {
+        assert_eq!(crate::scan!("11-22", "{}-{}", u64, u64), Ok((11, 22)));
+        assert_eq!(
+            crate::scan!("move 3 from 1 to 9", "move {} from {} to {}", u8, u8, u8),
+            Ok((3, 1, 9))
+        );
+        assert_eq!(
+            crate::scan!("<x=-1, name=abc>", "<x={}, name={}>", i32, String),
+            Ok((-1, "abc".to_string()))
+        );
+
+        assert!(crate::scan!("11-22", "{}:{}", u64, u64).is_err());
+        assert!(crate::scan!("a-22", "{}-{}", u64, u64).is_err());
+        assert!(crate::scan!("11-22", "{}-{}", u64).is_err());
+        assert!(crate::scan!("11-22", "{}", u64, u64).is_err());
+        assert!(crate::scan!("1122", "{}{}", u64, u64).is_err());
+    }
+
+    #[test]
+    fn parses_columns() {
+        let parsed: Vec<Vec<u32>> = columns("3   4\n4   3\n2   5").unwrap();
+        assert_eq!(parsed, vec![vec![3, 4, 2], vec![4, 3, 5]]);
+
+        assert_eq!(
+            columns::<u32>("1 2\n3").unwrap_err(),
+            ParseError::new(2, "expected 2 columns, found 1")
+        );
+        assert_eq!(
+            columns::<u32>("1 2\n3 4 5").unwrap_err(),
+            ParseError::new(2, "expected 2 columns, found more")
+        );
+        assert_eq!(columns::<u32>("1 x").unwrap_err().line, 1);
+    }
+}
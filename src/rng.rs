@@ -0,0 +1,146 @@
+//! A small, seedable pseudo-random number generator for property tests and generated inputs.
+//!
+//! The same seed always produces the same sequence, so failures can be reproduced.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64, which passes BigCrush and is fast enough to generate large inputs.
+/// Not suitable for cryptography.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds the generator from the current time, for runs that should differ from each other.
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        // NOTE: only the low bits change between runs, truncating is intended.
+        #[allow(clippy::cast_possible_truncation)]
+        Self::new(nanos as u64)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed value in `0..n`.
+    ///
+    /// # Panics
+    /// Panics if `n` is `0`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a value below 0");
+
+        // NOTE: rejects the values that would make the lower results more likely.
+        let limit = u64::MAX - u64::MAX % n;
+        loop {
+            let value = self.next_u64();
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+
+    /// A uniformly distributed value in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot pick a value from an empty range");
+
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+        let span = end.wrapping_sub(start) as u64;
+        let offset = match span.checked_add(1) {
+            Some(n) => self.below(n),
+            None => self.next_u64(),
+        };
+
+        #[allow(clippy::cast_possible_wrap)]
+        start.wrapping_add(offset as i64)
+    }
+
+    /// A uniformly distributed index into a collection of `len` elements.
+    pub fn index(&mut self, len: usize) -> usize {
+        // NOTE: `len` fits into a `u64` and the result is smaller than `len`.
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.below(len as u64) as usize;
+        index
+    }
+
+    /// `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random element of `items`, or [`None`] if it is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.index(items.len()))
+        }
+    }
+
+    /// Shuffles `items` in place with the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let mut a = Rng::new(42);
+        let mut b = Rng::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Rng::new(1).next_u64(), Rng::new(2).next_u64());
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 7];
+
+        for _ in 0..1000 {
+            let value = rng.range(-3..=3);
+            assert!((-3..=3).contains(&value));
+            seen[usize::try_from(value + 3).unwrap()] = true;
+        }
+
+        assert!(seen.iter().all(|&s| s));
+        assert!(rng.range(i64::MIN..=i64::MAX) != rng.range(i64::MIN..=i64::MAX));
+        assert_eq!(rng.range(5..=5), 5);
+    }
+
+    #[test]
+    fn shuffles_all_elements() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+        assert_eq!(rng.choose::<u32>(&[]), None);
+    }
+}
@@ -0,0 +1,212 @@
+/// A global allocator that counts allocations per thread, used to put allocation budgets on solutions in tests.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Allocations that were made while counting was active.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationCount {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Number of bytes requested over all allocations.
+    pub bytes: u64,
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+#[derive(Clone, Copy)]
+struct Counter {
+    active: bool,
+    count: AllocationCount,
+    current_bytes: i64,
+}
+
+impl Counter {
+    const IDLE: Self = Self {
+        active: false,
+        count: AllocationCount {
+            allocations: 0,
+            bytes: 0,
+            peak_bytes: 0,
+        },
+        current_bytes: 0,
+    };
+
+    fn record(&mut self, allocated: usize, freed: usize) {
+        if allocated > 0 {
+            self.count.allocations += 1;
+            self.count.bytes += allocated as u64;
+        }
+
+        #[allow(clippy::cast_possible_wrap)]
+        {
+            self.current_bytes += allocated as i64 - freed as i64;
+        }
+
+        #[allow(clippy::cast_sign_loss)]
+        {
+            self.count.peak_bytes = self.count.peak_bytes.max(self.current_bytes.max(0) as u64);
+        }
+    }
+}
+
+thread_local! {
+    // NOTE: const-initialized and without destructor, so accessing it never allocates.
+    static COUNTER: Cell<Counter> = const { Cell::new(Counter::IDLE) };
+}
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+fn record(allocated: usize, freed: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+
+    let _ = COUNTER.try_with(|cell| {
+        let mut counter = cell.get();
+        if counter.active {
+            counter.record(allocated, freed);
+            cell.set(counter);
+        }
+    });
+}
+
+/// Wraps the system allocator and counts allocations of the current thread while [`count_allocations`] runs.
+///
+/// The `solution!` macro installs this allocator in test builds (unless `dhat-heap` is enabled),
+/// so [`assert_allocations!`](crate::assert_allocations) can be used in each bin's tests.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0);
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size());
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size());
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Whether [`CountingAlloc`] is the global allocator of the running program.
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread.
+///
+/// The count is [`None`] if [`CountingAlloc`] is not the global allocator.
+/// Calls of this function must not be nested.
+pub fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, Option<AllocationCount>) {
+    COUNTER.with(|cell| {
+        cell.set(Counter {
+            active: true,
+            ..Counter::IDLE
+        });
+    });
+
+    let result = f();
+
+    let counter = COUNTER.with(|cell| cell.replace(Counter::IDLE));
+    let count = is_installed().then_some(counter.count);
+
+    (result, count)
+}
+
+/// Evaluates an expression and asserts that it makes at most the given number of allocations.
+/// Returns the value of the expression.
+///
+/// Fails if [`CountingAlloc`](crate::template::allocations::CountingAlloc) is not installed,
+/// e.g. when the tests run with `dhat-heap`, so an allocation budget can't be skipped unnoticed.
+///
+/// ```ignore
+/// let input = advent_of_code::template::read_file("examples", DAY);
+/// let result = advent_of_code::assert_allocations!(part_one(&input), <= 10);
+/// ```
+#[macro_export]
+macro_rules! assert_allocations {
+    ($expr:expr, <= $max:expr $(,)?) => {{
+        let (result, count) = $crate::template::allocations::count_allocations(|| $expr);
+        match count {
+            Some(count) => assert!(
+                count.allocations <= $max,
+                "`{}` made {} allocations ({} bytes), expected at most {}",
+                stringify!($expr),
+                count.allocations,
+                count.bytes,
+                $max
+            ),
+            None => panic!(
+                "allocation counting is not active, can't check `{}`. \
+                 Run the tests without `dhat-heap` to install the counting allocator.",
+                stringify!($expr)
+            ),
+        }
+        result
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CountingAlloc, count_allocations};
+
+    #[cfg(test)]
+    #[global_allocator]
+    static ALLOC: CountingAlloc = CountingAlloc;
+
+    #[test]
+    fn counts_allocations() {
+        let (_, count) = count_allocations(|| {
+            let a = vec![1_u8; 16];
+            let b = vec![2_u8; 32];
+            a.len() + b.len()
+        });
+        let count = count.unwrap();
+        assert_eq!(count.allocations, 2);
+        assert_eq!(count.bytes, 48);
+        assert_eq!(count.peak_bytes, 48);
+    }
+
+    #[test]
+    fn tracks_peak_bytes() {
+        let (_, count) = count_allocations(|| {
+            for size in [64, 16, 32] {
+                drop(vec![0_u8; size]);
+            }
+        });
+        let count = count.unwrap();
+        assert_eq!(count.allocations, 3);
+        assert_eq!(count.bytes, 112);
+        assert_eq!(count.peak_bytes, 64);
+    }
+
+    #[test]
+    fn counts_nothing_for_allocation_free_code() {
+        let (sum, count) = count_allocations(|| (1..=10_u64).sum::<u64>());
+        assert_eq!(sum, 55);
+        assert_eq!(count.unwrap().allocations, 0);
+    }
+
+    #[test]
+    fn passes_allocation_budget() {
+        let result = crate::assert_allocations!(vec![1, 2, 3], <= 1);
+        assert_eq!(result, vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "made 2 allocations")]
+    fn fails_allocation_budget() {
+        crate::assert_allocations!((vec![1], vec![2]), <= 1);
+    }
+}
@@ -1,5 +1,13 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::stars::{Stars, puzzle_count};
+use crate::template::{ANSI_BOLD, ANSI_RESET, all_days, aoc_cli, run_multi::run_multi};
 
 pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+    run_multi(&all_days().collect(), is_release, false, false);
+
+    let stars = Stars::read();
+    let max_stars = u32::from(puzzle_count(aoc_cli::get_year())) * 2;
+    println!(
+        "\n{ANSI_BOLD}Stars:{ANSI_RESET} {} / {max_stars}",
+        stars.total()
+    );
 }
@@ -0,0 +1,38 @@
+use std::process::{Command, Stdio};
+
+use crate::template::Day;
+
+pub fn handle(day: Day, iterations: Option<usize>, size: Option<usize>, seed: Option<u64>) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        // NOTE: overflows should show up as failures instead of wrapping silently.
+        "--profile".to_string(),
+        "checked".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--fuzz".to_string(),
+    ];
+
+    if let Some(iterations) = iterations {
+        cmd_args.extend(["--iterations".to_string(), iterations.to_string()]);
+    }
+    if let Some(size) = size {
+        cmd_args.extend(["--size".to_string(), size.to_string()]);
+    }
+    if let Some(seed) = seed {
+        cmd_args.extend(["--seed".to_string(), seed.to_string()]);
+    }
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -2,7 +2,7 @@ use std::process::{Command, Stdio};
 
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(day: Day, release: bool, dhat: bool, checked: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,6 +12,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
+    } else if checked {
+        // NOTE: release optimizations with overflow checks, so wrapping arithmetic can't produce a wrong answer.
+        cmd_args.extend(["--profile".to_string(), "checked".to_string()]);
     } else if release {
         cmd_args.push("--release".to_string());
     }
@@ -1,10 +1,20 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::process::{Command, Stdio};
 
+use crate::template::export::{self, ExportFormat};
 use crate::template::run_multi::run_multi;
+use crate::template::stars::Stars;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{Day, all_days, readme_benchmarks, readme_chart, readme_stars};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    dhat: bool,
+    export_to: Option<(ExportFormat, String)>,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
@@ -21,13 +31,33 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, dhat).unwrap();
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some((format, path)) = export_to {
+        // NOTE: only what was measured in this run, stored timings may be outdated.
+        match export::export(&timings, format, Path::new(&path)) {
+            Ok(()) => {
+                println!("Exported benchmarks as {format} to \"{path}\".");
+            }
+            Err(e) => {
+                eprintln!("Failed to export benchmarks: {e}");
+            }
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
         println!();
+        if let Err(e) = readme_chart::update(&merged_timings) {
+            eprintln!("Failed to update benchmarks chart: {e:?}");
+        }
+
+        if let Err(e) = readme_stars::update(&Stars::read()) {
+            eprintln!("Failed to update star calendar: {e:?}");
+        }
+
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
@@ -38,3 +68,27 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Times the solution of `day` on generated inputs of growing size.
+pub fn handle_scale(day: Day) {
+    let cmd_args = [
+        "run".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--scale".to_string(),
+    ];
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
@@ -0,0 +1,207 @@
+/// Module that exports timings to formats that are understood by other benchmarking tools.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{fs, io, path::Path};
+
+use crate::template::timings::{Timing, Timings};
+
+/// A file format that timings can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One row per solved part, with the duration in nanoseconds and memory stats if present.
+    Csv,
+    /// The `cargo bench` output format, which is also emitted by criterion's `--output-format bencher`.
+    /// The `(+/- ...)` deviation is left out, as it is not measured.
+    Bencher,
+}
+
+impl FromStr for ExportFormat {
+    type Err = ExportFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(Self::Csv),
+            "bencher" | "criterion" => Ok(Self::Bencher),
+            _ => Err(ExportFormatFromStrError),
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Csv => f.write_str("csv"),
+            Self::Bencher => f.write_str("bencher"),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`ExportFormat`].
+#[derive(Debug)]
+pub struct ExportFormatFromStrError;
+
+impl std::error::Error for ExportFormatFromStrError {}
+
+impl Display for ExportFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an export format of `csv` or `bencher`")
+    }
+}
+
+/// Writes `timings` to `path` in the given format.
+pub fn export(timings: &Timings, format: ExportFormat, path: &Path) -> Result<(), io::Error> {
+    let contents = match format {
+        ExportFormat::Csv => to_csv(timings),
+        ExportFormat::Bencher => to_bencher(timings),
+    };
+    fs::write(path, contents)
+}
+
+/// Iterates `(timing, part, nanos, formatted duration)` for every part that has a timing.
+fn timed_parts(timings: &Timings) -> impl Iterator<Item = (&Timing, u8, f64, &str)> {
+    timings.data.iter().flat_map(|timing| {
+        [(1, &timing.part_1), (2, &timing.part_2)]
+            .into_iter()
+            .filter_map(move |(part, formatted)| {
+                let formatted = formatted.as_deref()?;
+                let nanos = timing.part_nanos(part)?;
+                Some((timing, part, nanos, formatted))
+            })
+    })
+}
+
+fn to_csv(timings: &Timings) -> String {
+    let mut lines = vec!["day,part,time,nanos,peak_bytes,allocations".to_string()];
+
+    for (timing, part, nanos, formatted) in timed_parts(timings) {
+        let memory = timing.part_stats(part).and_then(|stats| stats.memory);
+
+        lines.push(format!(
+            "{},{part},{formatted},{nanos:.0},{},{}",
+            timing.day,
+            memory.map_or_else(String::new, |m| m.peak_bytes.to_string()),
+            memory.map_or_else(String::new, |m| m.allocations.to_string()),
+        ));
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+fn to_bencher(timings: &Timings) -> String {
+    let mut lines: Vec<String> = timed_parts(timings)
+        .map(|(timing, part, nanos, _)| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let nanos = nanos.round() as u64;
+            format!(
+                "test day_{}::part_{part} ... bench: {:>11} ns/iter",
+                timing.day,
+                group_thousands(nanos)
+            )
+        })
+        .collect();
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Formats a number with `,` as thousands separator, as done by `cargo bench`.
+fn group_thousands(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    grouped
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExportFormat, group_thousands, to_bencher, to_csv};
+    use crate::{
+        day,
+        template::runner::MemoryStats,
+        template::timings::{PartStats, Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("74.13µs".into()),
+                    part_2: Some("1.5ms".into()),
+                    total_nanos: 1_574_130_f64,
+                    part_1_stats: PartStats {
+                        memory: Some(MemoryStats {
+                            allocations: 3,
+                            peak_bytes: 2048,
+                        }),
+                        ..PartStats::default()
+                    },
+                    part_2_stats: PartStats::default(),
+                    parse: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("12ns".into()),
+                    part_2: None,
+                    total_nanos: 12_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<ExportFormat>().unwrap(), ExportFormat::Csv);
+        assert_eq!(
+            "criterion".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Bencher
+        );
+        assert!("xml".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let expected = [
+            "day,part,time,nanos,peak_bytes,allocations",
+            "01,1,74.13µs,74130,2048,3",
+            "01,2,1.5ms,1500000,,",
+            "02,1,12ns,12,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(to_csv(&get_mock_timings()), expected);
+    }
+
+    #[test]
+    fn exports_bencher() {
+        let expected = [
+            "test day_01::part_1 ... bench:      74,130 ns/iter",
+            "test day_01::part_2 ... bench:   1,500,000 ns/iter",
+            "test day_02::part_1 ... bench:          12 ns/iter",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(to_bencher(&get_mock_timings()), expected);
+    }
+
+    #[test]
+    fn groups_thousands() {
+        assert_eq!(group_thousands(0), "0");
+        assert_eq!(group_thousands(999), "999");
+        assert_eq!(group_thousands(1000), "1,000");
+        assert_eq!(group_thousands(1_234_567), "1,234,567");
+    }
+}
@@ -0,0 +1,368 @@
+//! Runs solutions against generated inputs to find panics and wrong answers.
+//!
+//! Started with `cargo fuzz-day NN`, which runs the solution binary with `--fuzz`.
+//! A failing input is shrunk to a small example that still fails, and written to `data/fuzz/`.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+use std::{fs, process};
+
+use crate::rng::Rng;
+use crate::template::generator::{GenerateFn, generate_input};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, InputMode};
+
+const DEFAULT_ITERATIONS: usize = 200;
+const DEFAULT_MAX_SIZE: usize = 50;
+
+/// Maximum number of times a failing input is re-run while shrinking it.
+const SHRINK_BUDGET: usize = 5000;
+
+/// A solution part that takes an input and returns its formatted answer.
+pub type PartFn<'a> = &'a dyn Fn(&str) -> Option<String>;
+
+/// A part of the solution under test, created by [`solution!`](crate::solution).
+pub struct FuzzPart<'a> {
+    pub part: u8,
+    pub solve: PartFn<'a>,
+    /// A slower but trusted implementation whose answers the solution must match.
+    pub reference: Option<PartFn<'a>>,
+}
+
+/// Options of a fuzzing run, read from the arguments of the solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzOptions {
+    pub iterations: usize,
+    /// Size of the last generated input, earlier inputs are smaller.
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl FuzzOptions {
+    /// Returns the options if the binary was started with `--fuzz`.
+    pub fn from_args() -> Option<Self> {
+        let mut args = pico_args::Arguments::from_env();
+        if !args.contains("--fuzz") {
+            return None;
+        }
+
+        let iterations = args
+            .opt_value_from_str("--iterations")
+            .unwrap_or_else(exit_with_error);
+        let max_size = args
+            .opt_value_from_str("--size")
+            .unwrap_or_else(exit_with_error);
+        let seed = args
+            .opt_value_from_str("--seed")
+            .unwrap_or_else(exit_with_error);
+
+        Some(Self {
+            iterations: iterations.unwrap_or(DEFAULT_ITERATIONS),
+            max_size: max_size.unwrap_or(DEFAULT_MAX_SIZE),
+            seed: seed.unwrap_or_else(|| Rng::from_time().next_u64()),
+        })
+    }
+}
+
+pub(crate) fn exit_with_error<T>(e: pico_args::Error) -> T {
+    eprintln!("Error: {e}");
+    process::exit(1);
+}
+
+/// Why a part failed on an input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Failure {
+    Panic {
+        message: String,
+        location: Option<String>,
+    },
+    Mismatch {
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+}
+
+impl Failure {
+    /// Whether `other` is most likely the same bug, so shrinking does not drift to a different one.
+    fn same_kind(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Panic { location: a, .. }, Self::Panic { location: b, .. }) => a == b,
+            (Self::Mismatch { .. }, Self::Mismatch { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic { message, location } => {
+                write!(f, "panicked")?;
+                if let Some(location) = location {
+                    write!(f, " at {location}")?;
+                }
+                write!(f, ": {message}")
+            }
+            Self::Mismatch { expected, actual } => {
+                write!(
+                    f,
+                    "answered {actual:?}, but the reference answered {expected:?}"
+                )
+            }
+        }
+    }
+}
+
+thread_local! {
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `func`, turning a panic into a [`Failure`].
+fn catch(func: PartFn, input: &str) -> Result<Option<String>, Failure> {
+    panic::catch_unwind(AssertUnwindSafe(|| func(input))).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+
+        Failure::Panic {
+            message,
+            location: PANIC_LOCATION.with(|l| l.borrow_mut().take()),
+        }
+    })
+}
+
+fn check(part: &FuzzPart, input: &str) -> Option<Failure> {
+    let actual = match catch(part.solve, input) {
+        Ok(actual) => actual,
+        Err(failure) => return Some(failure),
+    };
+
+    // NOTE: a panicking reference can't tell us anything about the solution.
+    let expected = catch(part.reference?, input).ok()?;
+
+    (expected != actual).then_some(Failure::Mismatch { expected, actual })
+}
+
+/// Runs all `parts` on generated inputs of growing size and exits with an error on the first failure.
+pub fn run(
+    day: Day,
+    mode: InputMode,
+    generator: Option<GenerateFn>,
+    parts: &[FuzzPart],
+    options: &FuzzOptions,
+) {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Add one with `solution!({}, generator = ...)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    println!(
+        "Fuzzing day {day} with {} inputs up to size {} (seed {}).",
+        options.iterations, options.max_size, options.seed
+    );
+
+    // NOTE: panics are expected here, only remember where they happened instead of printing them.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let location = info.location().map(ToString::to_string);
+        PANIC_LOCATION.with(|l| *l.borrow_mut() = location);
+    }));
+
+    let mut rng = Rng::new(options.seed);
+    let mut found = None;
+
+    'inputs: for iteration in 0..options.iterations {
+        // small inputs first, their failures are easier to understand.
+        let size = 1 + iteration * options.max_size / options.iterations.max(1);
+        let input = generate_input(generator, &mut rng, size, mode);
+
+        for part in parts {
+            if let Some(failure) = check(part, &input) {
+                found = Some((part, input, failure));
+                break 'inputs;
+            }
+        }
+    }
+
+    let result = found.map(|(part, input, failure)| {
+        let input = shrink(&input, |candidate| {
+            check(part, candidate).is_some_and(|f| f.same_kind(&failure))
+        });
+        let failure = check(part, &input).unwrap_or(failure);
+        (part.part, input, failure)
+    });
+
+    panic::set_hook(default_hook);
+
+    match result {
+        None => println!("No failures in {} inputs.", options.iterations),
+        Some((part, input, failure)) => {
+            eprintln!("{ANSI_BOLD}Part {part}{ANSI_RESET} {failure}");
+
+            let path = format!("data/fuzz/{day}-{part}.txt");
+            match fs::create_dir_all("data/fuzz").and_then(|()| fs::write(&path, &input)) {
+                Ok(()) => eprintln!("Wrote a minimal failing input to {path}:\n{input}"),
+                Err(e) => eprintln!("Failed to write {path}: {e}\n{input}"),
+            }
+
+            process::exit(1);
+        }
+    }
+}
+
+/// Shrinks `input` while `fails` holds, first by removing lines and then by making numbers smaller.
+fn shrink(input: &str, mut fails: impl FnMut(&str) -> bool) -> String {
+    let mut budget = SHRINK_BUDGET;
+    let mut fails = |candidate: &str| {
+        budget = budget.saturating_sub(1);
+        budget > 0 && fails(candidate)
+    };
+
+    let mut lines: Vec<String> = input.split('\n').map(ToString::to_string).collect();
+
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<String> = [&lines[..start], &lines[end..]].concat();
+
+            if fails(&candidate.join("\n")) {
+                lines = candidate;
+            } else {
+                start += chunk;
+            }
+        }
+        chunk /= 2;
+    }
+
+    let mut input = lines.join("\n");
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        let mut start = 0;
+        while let Some(offset) = input[start..].find(|c: char| c.is_ascii_digit()) {
+            let from = start + offset;
+            let to = input[from..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(input.len(), |len| from + len);
+
+            for smaller in smaller_numbers(&input[from..to]) {
+                let candidate = format!("{}{smaller}{}", &input[..from], &input[to..]);
+                if fails(&candidate) {
+                    input = candidate;
+                    changed = true;
+                    break;
+                }
+            }
+
+            start = input[from..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(input.len(), |len| from + len);
+        }
+    }
+
+    input
+}
+
+/// Candidates for a number that are smaller than `digits`, smallest first.
+fn smaller_numbers(digits: &str) -> Vec<String> {
+    let Ok(value) = digits.parse::<u128>() else {
+        return vec![];
+    };
+
+    let mut candidates = vec![0, 1, value / 2, value.saturating_sub(1)];
+    candidates.retain(|&c| c < value);
+    candidates.dedup();
+    candidates.into_iter().map(|c| c.to_string()).collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Failure, FuzzPart, check, shrink};
+    use crate::parse::ints;
+
+    #[test]
+    fn shrinks_to_failing_lines() {
+        let input = "1\n2\n3\n40\n5\n6\n7";
+        let shrunk = shrink(input, |candidate| {
+            candidate
+                .lines()
+                .any(|l| l.parse::<u32>().is_ok_and(|n| n >= 10))
+        });
+        assert_eq!(shrunk, "10");
+    }
+
+    #[test]
+    fn shrinks_numbers_within_lines() {
+        let input = "a 1000 b 77\nc 3";
+        let shrunk = shrink(input, |candidate| {
+            ints::<u32>(candidate).nth(1).is_some_and(|n| n >= 7)
+        });
+        assert_eq!(shrunk, "a 0 b 7");
+    }
+
+    #[test]
+    fn detects_failures() {
+        let sum = |input: &str| -> Option<String> {
+            Some(
+                input
+                    .lines()
+                    .map(|l| l.parse::<u8>().unwrap())
+                    .fold(0_u8, |sum, n| sum.checked_add(n).unwrap())
+                    .to_string(),
+            )
+        };
+        let reference = |input: &str| -> Option<String> {
+            Some(
+                input
+                    .lines()
+                    .map(|l| l.parse::<u32>().unwrap())
+                    .sum::<u32>()
+                    .to_string(),
+            )
+        };
+        let part = FuzzPart {
+            part: 1,
+            solve: &sum,
+            reference: Some(&reference),
+        };
+
+        assert_eq!(check(&part, "1\n2"), None);
+        assert!(matches!(check(&part, "x"), Some(Failure::Panic { .. })));
+        assert!(matches!(check(&part, "300"), Some(Failure::Panic { .. })));
+        assert!(matches!(
+            check(&part, "200\n100"),
+            Some(Failure::Panic { .. })
+        ));
+
+        let wrapping = |input: &str| -> Option<String> {
+            let sum = input
+                .lines()
+                .map(|l| l.parse::<u8>().unwrap())
+                .fold(0_u8, u8::wrapping_add);
+            Some(sum.to_string())
+        };
+        let part = FuzzPart {
+            part: 1,
+            solve: &wrapping,
+            reference: Some(&reference),
+        };
+        assert_eq!(
+            check(&part, "200\n100"),
+            Some(Failure::Mismatch {
+                expected: Some("300".to_string()),
+                actual: Some("44".to_string()),
+            })
+        );
+    }
+}
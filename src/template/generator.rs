@@ -0,0 +1,22 @@
+//! Random puzzle inputs, used for fuzzing solutions.
+
+use crate::rng::Rng;
+use crate::template::{InputMode, normalize_input};
+
+/// Generates random inputs for a day. Enable it with `solution!(NN, generator = MyGenerator)`.
+pub trait Generator {
+    /// A random input in the format of the puzzle input.
+    ///
+    /// `size` controls the amount of work, e.g. the number of lines, so that a solution
+    /// takes longer the larger it is.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// Signature of [`Generator::generate`], so a generator can be passed around as a function.
+pub type GenerateFn = fn(&mut Rng, usize) -> String;
+
+/// Generates an input and normalizes it like an input file that was read from disk.
+pub fn generate_input(generate: GenerateFn, rng: &mut Rng, size: usize, mode: InputMode) -> String {
+    let input = generate(rng, size);
+    normalize_input(&input, mode).into_owned()
+}
@@ -0,0 +1,132 @@
+/// Normalization of puzzle inputs before they are handed to a solution.
+use std::borrow::Cow;
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Controls how an input file is normalized after it has been read from disk.
+///
+/// Each mode includes the normalization steps of the modes listed before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputMode {
+    /// The file contents, exactly as stored on disk.
+    #[default]
+    Raw,
+    /// Strips a leading byte order mark and converts `\r\n` line endings to `\n`.
+    Normalized,
+    /// Like [`InputMode::Normalized`], with trailing newlines removed.
+    Trimmed,
+}
+
+/// Applies the normalization steps of `mode` to `input`.
+///
+/// ```
+/// # use advent_of_code::template::{InputMode, normalize_input};
+/// let input = "\u{feff}1-2,3-4\r\n";
+/// assert_eq!(normalize_input(input, InputMode::Raw), input);
+/// assert_eq!(normalize_input(input, InputMode::Normalized), "1-2,3-4\n");
+/// assert_eq!(normalize_input(input, InputMode::Trimmed), "1-2,3-4");
+/// ```
+#[must_use]
+pub fn normalize_input(input: &str, mode: InputMode) -> Cow<'_, str> {
+    if mode == InputMode::Raw {
+        return Cow::Borrowed(input);
+    }
+
+    let input = input.strip_prefix(BYTE_ORDER_MARK).unwrap_or(input);
+
+    let normalized = if input.contains("\r\n") {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    };
+
+    match (mode, normalized) {
+        (InputMode::Trimmed, Cow::Borrowed(s)) => Cow::Borrowed(s.trim_end_matches('\n')),
+        (InputMode::Trimmed, Cow::Owned(s)) => Cow::Owned(s.trim_end_matches('\n').to_string()),
+        (_, normalized) => normalized,
+    }
+}
+
+/// A character that is unlikely to be part of a puzzle input, together with its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SuspiciousChar {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    pub char: char,
+}
+
+/// Returns the first character that is neither printable ASCII nor a line feed, if any.
+///
+/// Puzzle inputs are plain ASCII, so carriage returns, tabs, byte order marks or
+/// non-breaking spaces usually mean that the file was mangled by an editor or a copy & paste.
+#[must_use]
+pub fn find_suspicious_char(input: &str) -> Option<SuspiciousChar> {
+    input.split('\n').enumerate().find_map(|(line_idx, line)| {
+        line.chars()
+            .enumerate()
+            .find(|&(_, c)| !(c == ' ' || c.is_ascii_graphic()))
+            .map(|(col_idx, c)| SuspiciousChar {
+                line: line_idx + 1,
+                column: col_idx + 1,
+                char: c,
+            })
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputMode, SuspiciousChar, find_suspicious_char, normalize_input};
+
+    #[test]
+    fn keeps_raw_input() {
+        let input = "a\r\nb\r\n\r\n";
+        assert_eq!(normalize_input(input, InputMode::Raw), input);
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        let input = "a\r\nb\r\n\r\nc\n";
+        assert_eq!(normalize_input(input, InputMode::Normalized), "a\nb\n\nc\n");
+    }
+
+    #[test]
+    fn strips_byte_order_mark() {
+        assert_eq!(
+            normalize_input("\u{feff}a\nb\n", InputMode::Normalized),
+            "a\nb\n"
+        );
+    }
+
+    #[test]
+    fn trims_trailing_newlines() {
+        assert_eq!(normalize_input("a\nb\n\n", InputMode::Trimmed), "a\nb");
+        assert_eq!(normalize_input("a\r\nb\r\n", InputMode::Trimmed), "a\nb");
+        assert_eq!(normalize_input("  a  \n", InputMode::Trimmed), "  a  ");
+    }
+
+    #[test]
+    fn accepts_plain_ascii() {
+        assert_eq!(find_suspicious_char("123 328  51\n*   +   *\n"), None);
+    }
+
+    #[test]
+    fn reports_suspicious_chars() {
+        assert_eq!(
+            find_suspicious_char("ab\ncd\tef\n"),
+            Some(SuspiciousChar {
+                line: 2,
+                column: 3,
+                char: '\t'
+            })
+        );
+        assert_eq!(find_suspicious_char("a\r\n").map(|s| s.char), Some('\r'));
+        assert_eq!(
+            find_suspicious_char("\u{feff}a").map(|s| s.char),
+            Some('\u{feff}')
+        );
+    }
+}
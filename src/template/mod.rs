@@ -1,14 +1,25 @@
 use std::{env, fs};
 
+pub mod allocations;
 pub mod aoc_cli;
 pub mod commands;
+pub mod export;
+pub mod fuzz;
+pub mod generator;
 pub mod runner;
+pub mod scaling;
 
 pub use day::*;
+pub use input::*;
 
 mod day;
+mod input;
 mod readme_benchmarks;
+mod readme_chart;
+mod readme_config;
+mod readme_stars;
 mod run_multi;
+mod stars;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -24,6 +35,24 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Helper function that reads a text file to a string and normalizes it according to `mode`.
+///
+/// Prints a warning if the normalized input still contains characters that are unusual for puzzle inputs.
+#[must_use]
+pub fn read_file_normalized(folder: &str, day: Day, mode: InputMode) -> String {
+    let input = read_file(folder, day);
+    let input = normalize_input(&input, mode).into_owned();
+
+    if let Some(suspicious) = find_suspicious_char(&input) {
+        eprintln!(
+            "Warning: data/{folder}/{day}.txt contains suspicious character {:?} at line {}, column {}.",
+            suspicious.char, suspicious.line, suspicious.column
+        );
+    }
+
+    input
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -36,33 +65,158 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `INPUT_MODE` and sets up the input and runner for each part.
+/// In test builds, allocations are counted so that [`assert_allocations!`](crate::assert_allocations) can be used.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Options can be passed as trailing `key = value` pairs:
+///  - `input = Normalized | Trimmed` selects the [`InputMode`](crate::template::InputMode) that
+///    is applied to the input before it is passed to the solution. Defaults to `Raw`.
+///  - `generator = Type` sets a [`Generator`](crate::template::generator::Generator) for random inputs,
+///    which enables fuzzing the solution with `cargo fuzz-day NN` and timing it on growing
+///    inputs with `cargo time NN --scale`.
+///  - `parse = function` names the parser of the day, e.g. `fn parse_input(input: &str) -> Grid<Cell>`.
+///    It is benched as the parse time of the day with `cargo time`.
+///  - `reference = module` names a module with its own `part_one` and `part_two`,
+///    whose answers the solution must match while fuzzing.
+///  - `report = function` names a function that describes how the day solves its input,
+///    e.g. `fn rounds_report(input: &str) -> String`. It is printed when running with `--report`.
+///
+/// Unknown options are a compile error.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr, 1 $(, $key:ident = $value:tt)* $(,)?) => {
+        $crate::solution!(@impl $day, [part_one, 1]; { $($key = $value),* });
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($day:expr, 2 $(, $key:ident = $value:tt)* $(,)?) => {
+        $crate::solution!(@impl $day, [part_two, 2]; { $($key = $value),* });
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($day:expr $(, $key:ident = $value:tt)* $(,)?) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]; { $($key = $value),* });
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // NOTE: the options are passed around as a single `{ key = value, .. }` group,
+    // so they can be used within the repetition over the parts.
+    (@impl $day:expr, $( [$func:ident, $part:expr] )*; $options:tt) => {
+        $crate::solution!(@validate $options);
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The normalization applied to inputs of the current day.
+        const INPUT_MODE: $crate::template::InputMode = $crate::solution!(@input_mode $options);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(test, not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::allocations::CountingAlloc =
+            $crate::template::allocations::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
+
+            if let Some(options) = $crate::template::fuzz::FuzzOptions::from_args() {
+                let parts = [$(
+                    $crate::template::fuzz::FuzzPart {
+                        part: $part,
+                        solve: &|input: &str| $func(input).map(|answer| answer.to_string()),
+                        reference: $crate::solution!(@reference $func; $options),
+                    },
+                )*];
+                let generator = $crate::solution!(@generator $options);
+                $crate::template::fuzz::run(DAY, INPUT_MODE, generator, &parts, &options);
+                return;
+            }
+
+            if let Some(options) = $crate::template::scaling::ScaleOptions::from_args() {
+                let parts: &[(u8, $crate::template::fuzz::PartFn)] = &[$(
+                    ($part, &|input: &str| $func(input).map(|answer| answer.to_string())),
+                )*];
+                let generator = $crate::solution!(@generator $options);
+                $crate::template::scaling::run(DAY, INPUT_MODE, generator, parts, &options);
+                return;
+            }
+
+            let input = read_input(DAY, INPUT_MODE);
+            $crate::solution!(@parse input; $options);
+            $crate::solution!(@report input; $options);
             $( run_part($func, &input, DAY, $part); )*
         }
     };
+
+    (@validate {}) => {};
+    (@validate { input = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@validate { $($key = $value),* });
+    };
+    (@validate { parse = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@validate { $($key = $value),* });
+    };
+    (@validate { generator = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@validate { $($key = $value),* });
+    };
+    (@validate { reference = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@validate { $($key = $value),* });
+    };
+    (@validate { report = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@validate { $($key = $value),* });
+    };
+    (@validate { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        compile_error!(concat!(
+            "unknown solution! option `",
+            stringify!($other),
+            "`, expected `input`, `parse`, `generator`, `reference` or `report`"
+        ));
+    };
+
+    // NOTE: the remaining arms skip unknown options, as `@validate` already rejected them.
+    (@input_mode {}) => {
+        $crate::template::InputMode::Raw
+    };
+    (@input_mode { input = $mode:ident $(, $key:ident = $value:tt)* }) => {
+        $crate::template::InputMode::$mode
+    };
+    (@input_mode { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@input_mode { $($key = $value),* })
+    };
+
+    (@parse $input:ident; {}) => {};
+    (@parse $input:ident; { parse = $func:ident $(, $key:ident = $value:tt)* }) => {
+        run_parse($func, &$input);
+    };
+    (@parse $input:ident; { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@parse $input; { $($key = $value),* })
+    };
+
+    (@report $input:ident; {}) => {};
+    (@report $input:ident; { report = $func:ident $(, $key:ident = $value:tt)* }) => {
+        run_report($func, &$input);
+    };
+    (@report $input:ident; { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@report $input; { $($key = $value),* })
+    };
+
+    (@generator {}) => {
+        None
+    };
+    (@generator { generator = $generator:ident $(, $key:ident = $value:tt)* }) => {
+        Some(<$generator as $crate::template::generator::Generator>::generate
+            as $crate::template::generator::GenerateFn)
+    };
+    (@generator { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@generator { $($key = $value),* })
+    };
+
+    (@reference $func:ident; {}) => {
+        None
+    };
+    (@reference $func:ident; { reference = $module:ident $(, $key:ident = $value:tt)* }) => {
+        Some(&|input: &str| $module::$func(input).map(|answer| answer.to_string()))
+    };
+    (@reference $func:ident; { $other:ident = $other_value:tt $(, $key:ident = $value:tt)* }) => {
+        $crate::solution!(@reference $func; { $($key = $value),* })
+    };
 }
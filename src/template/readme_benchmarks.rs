@@ -1,9 +1,12 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io, time::Duration};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::readme_config::{Column, SortOrder, TableConfig, TimeUnit};
+use crate::template::runner::format_bytes;
+use crate::template::stars::Stars;
+use crate::template::timings::{PartStats, Timing, Timings, parse_duration_nanos};
+use crate::template::{Day, aoc_cli};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,8 +24,8 @@ impl From<std::io::Error> for Error {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -30,60 +33,232 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates a README section that is delimited by two occurrences of `marker`, including the markers.
+pub fn locate_section(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
         .first()
         .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find start position.")))?;
 
     let pos_end = matches
         .last()
         .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .ok_or_else(|| Error::Parser(format!("{marker}: could not find end position.")))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Data for the table that is not part of the timings.
+#[derive(Default)]
+pub struct TableContext {
+    /// Puzzle titles, read from the puzzle descriptions.
+    pub puzzle_titles: HashMap<Day, String>,
+    /// Used to link puzzle titles to the puzzle on adventofcode.com.
+    pub year: Option<u16>,
+    /// Earned stars, read from the recorded answers and puzzle descriptions.
+    pub stars: Stars,
+}
+
+impl TableContext {
+    fn read(timings: &Timings) -> Self {
+        let puzzle_titles = timings
+            .data
+            .iter()
+            .filter_map(|t| read_puzzle_title(t.day).map(|title| (t.day, title)))
+            .collect();
+
+        Self {
+            puzzle_titles,
+            year: aoc_cli::get_year(),
+            stars: Stars::read(),
+        }
+    }
+}
+
+/// Extracts the title from a puzzle description, e.g. `Secret Entrance` from `## --- Day 1: Secret Entrance ---`.
+fn parse_puzzle_title(description: &str) -> Option<String> {
+    description.lines().find_map(|line| {
+        let title = line
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix("--- Day ")?
+            .strip_suffix(" ---")?
+            .split_once(": ")?
+            .1;
+        Some(title.to_string())
+    })
+}
+
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let description = fs::read_to_string(format!("./data/puzzles/{day}.md")).ok()?;
+    parse_puzzle_title(&description)
+}
+
+fn format_time(formatted: Option<&String>, unit: Option<TimeUnit>) -> String {
+    match (formatted, unit) {
+        (None, _) => "-".into(),
+        (Some(formatted), None) => formatted.clone(),
+        (Some(formatted), Some(unit)) => {
+            parse_duration_nanos(formatted).map_or_else(|| formatted.clone(), |n| unit.format(n))
+        }
+    }
+}
+
+fn format_nanos(nanos: f64, unit: Option<TimeUnit>) -> String {
+    match unit {
+        Some(unit) => unit.format(nanos),
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        None => format!("{:.1?}", Duration::from_nanos(nanos as u64)),
+    }
+}
+
+fn format_peak_memory(stats: &PartStats) -> String {
+    stats
+        .memory
+        .map_or_else(|| "-".into(), |memory| format_bytes(memory.peak_bytes))
+}
+
+fn format_samples(stats: &PartStats) -> String {
+    stats
+        .samples
+        .map_or_else(|| "-".into(), |samples| samples.to_string())
+}
+
+fn format_cell(
+    column: Column,
+    timing: &Timing,
+    config: &TableConfig,
+    ctx: &TableContext,
+) -> String {
+    let per_part = |f: &dyn Fn(&PartStats) -> String| {
+        format!(
+            "`{}` / `{}`",
+            f(&timing.part_1_stats),
+            f(&timing.part_2_stats)
+        )
+    };
+
+    match column {
+        Column::Part1 => format!("`{}`", format_time(timing.part_1.as_ref(), config.unit)),
+        Column::Part2 => format!("`{}`", format_time(timing.part_2.as_ref(), config.unit)),
+        Column::Median => per_part(&|stats| format_time(stats.median.as_ref(), config.unit)),
+        Column::Samples => per_part(&format_samples),
+        Column::Memory => per_part(&format_peak_memory),
+        Column::Parse => format!("`{}`", format_time(timing.parse.as_ref(), config.unit)),
+        Column::Stars => match ctx.stars.day(timing.day) {
+            0 => "-".into(),
+            stars => "⭐".repeat(stars.into()),
+        },
+        Column::Puzzle => match (ctx.puzzle_titles.get(&timing.day), ctx.year) {
+            (Some(title), Some(year)) => format!(
+                "[{title}](https://adventofcode.com/{year}/day/{})",
+                timing.day.into_inner()
+            ),
+            (Some(title), None) => title.clone(),
+            (None, _) => "-".into(),
+        },
+    }
+}
+
+fn format_part_totals(columns: &[Column], timings: &Timings, unit: Option<TimeUnit>) -> String {
+    let part_total =
+        |part: u8| -> f64 { timings.data.iter().filter_map(|t| t.part_nanos(part)).sum() };
+
+    let cells: Vec<String> = columns
+        .iter()
+        .map(|column| match column {
+            Column::Part1 => format!("**`{}`**", format_nanos(part_total(1), unit)),
+            Column::Part2 => format!("**`{}`**", format_nanos(part_total(2), unit)),
+            _ => String::new(),
+        })
+        .collect();
+
+    format!("| **Total** | {} |", cells.join(" | "))
+}
+
+fn construct_table(
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    ctx: &TableContext,
+) -> String {
+    let header = format!("{} {}", "#".repeat(config.heading_level), config.title);
+
+    let columns = config.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![Column::Part1, Column::Part2];
+        if timings.has_memory_stats() {
+            columns.push(Column::Memory);
+        }
+        columns
+    });
+
+    let headers: Vec<&str> = columns.iter().map(|c| c.header()).collect();
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | {} |", headers.join(" | ")),
+        format!("| :---: | {}  |", vec![":---:"; columns.len()].join(" | ")),
     ];
 
-    for timing in timings.data {
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+
+    match config.sort {
+        SortOrder::Day => data.sort_by_key(|t| t.day),
+        SortOrder::Total => data.sort_by(|a, b| a.total_nanos.total_cmp(&b.total_nanos)),
+        SortOrder::TotalDesc => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+
+    for timing in data {
         let path = get_path_for_bin(timing.day);
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| format_cell(*column, timing, config, ctx))
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            cells.join(" | ")
         ));
     }
 
+    if config.part_totals {
+        lines.push(format_part_totals(&columns, &timings, config.unit));
+    }
+
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    match config.unit {
+        Some(unit) => lines.push(format!(
+            "**Total: {}**",
+            unit.format(total_millis * 1_000_000_f64)
+        )),
+        None => lines.push(format!("**Total: {total_millis:.2}ms**")),
+    }
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+    ctx: &TableContext,
+) -> Result<(), Error> {
+    let positions = locate_section(s, MARKER)?;
+    let table = construct_table(timings, total_millis, config, ctx);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -92,15 +267,32 @@ pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let config = TableConfig::read_from_file();
+    let ctx = TableContext::read(&timings);
+    update_content(&mut readme, timings, total_millis, &config, &ctx)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::collections::HashMap;
+
+    use super::{MARKER, TableContext, parse_puzzle_title, update_content};
+    use crate::{
+        day,
+        template::readme_config::{Column, SortOrder, TableConfig, TimeUnit},
+        template::runner::MemoryStats,
+        template::timings::{PartStats, Timing, Timings},
+    };
+
+    fn config() -> TableConfig {
+        TableConfig::default()
+    }
+
+    fn ctx() -> TableContext {
+        TableContext::default()
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +302,27 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 },
             ],
         }
@@ -131,28 +332,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +361,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &config(), &ctx()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -180,4 +381,83 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_stats.memory = Some(MemoryStats {
+            allocations: 3,
+            peak_bytes: 1536,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config(), &ctx()).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | Peak memory |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `1.5 KiB` / `-` |"
+        );
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_1_stats.samples = Some(100);
+        timings.data[1].part_2_stats.median = Some("35ms".into());
+        timings.data[1].part_2 = None;
+
+        let config = TableConfig {
+            heading_level: 3,
+            title: "Timings".into(),
+            columns: Some(vec![
+                Column::Puzzle,
+                Column::Part1,
+                Column::Part2,
+                Column::Median,
+                Column::Samples,
+                Column::Stars,
+            ]),
+            unit: Some(TimeUnit::Micros),
+            sort: SortOrder::TotalDesc,
+            part_totals: true,
+        };
+
+        let ctx = TableContext {
+            puzzle_titles: HashMap::from([(day!(2), "Gift Shop".to_string())]),
+            year: Some(2025),
+            stars: [(day!(1), 2), (day!(2), 1)].into_iter().collect(),
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config, &ctx).unwrap();
+
+        let expected = [
+            "<!--- benchmarking table --->",
+            "### Timings",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 | Median | Samples | Stars |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 4](./src/bin/04.rs) | - | `40000.00µs` | `50000.00µs` | `-` / `-` | `-` / `-` | - |",
+            "| [Day 2](./src/bin/02.rs) | [Gift Shop](https://adventofcode.com/2025/day/2) | `30000.00µs` | `-` | `-` / `35000.00µs` | `100` / `-` | ⭐ |",
+            "| [Day 1](./src/bin/01.rs) | - | `10000.00µs` | `20000.00µs` | `-` / `-` | `-` / `-` | ⭐⭐ |",
+            "| **Total** |  | **`80000.00µs`** | **`70000.00µs`** |  |  |  |",
+            "",
+            "**Total: 190000.00µs**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn parses_puzzle_titles() {
+        let description = "## --- Day 1: Secret Entrance ---\nThe Elves have good news...";
+        assert_eq!(
+            parse_puzzle_title(description),
+            Some("Secret Entrance".to_string())
+        );
+        assert_eq!(parse_puzzle_title("# Some other file"), None);
+    }
 }
@@ -0,0 +1,345 @@
+/// Module that renders solution times as a bar chart, both as an SVG file and as an ASCII fallback in the readme.
+/// Times are plotted on a logarithmic scale, so that fast and slow solutions fit in the same chart.
+use std::fmt::Write;
+use std::fs;
+
+use crate::template::readme_benchmarks::{Error, locate_section};
+use crate::template::timings::Timings;
+
+static MARKER: &str = "<!--- benchmarking chart --->";
+static SVG_PATH: &str = ".assets/benchmarks.svg";
+
+const ASCII_WIDTH: usize = 40;
+const PART_SYMBOLS: [char; 2] = ['#', '='];
+
+const SVG_WIDTH: f64 = 640.0;
+const SVG_LABEL_WIDTH: f64 = 64.0;
+const SVG_VALUE_WIDTH: f64 = 72.0;
+const SVG_HEADER_HEIGHT: f64 = 40.0;
+const SVG_BAR_HEIGHT: f64 = 10.0;
+const SVG_DAY_GAP: f64 = 8.0;
+const SVG_COLORS: [&str; 2] = ["#f9c74f", "#577590"];
+
+/// Times of both parts of a day, in nanoseconds.
+struct Row {
+    day: u8,
+    parts: [Option<f64>; 2],
+}
+
+/// A logarithmic scale that spans whole decades of nanoseconds.
+struct LogScale {
+    min_exp: i32,
+    max_exp: i32,
+}
+
+impl LogScale {
+    fn new(rows: &[Row]) -> Option<Self> {
+        let values = || rows.iter().flat_map(|r| r.parts.iter().flatten().copied());
+
+        values().next()?;
+
+        let min = values().fold(f64::INFINITY, f64::min).max(1.0);
+        let max = values().fold(0_f64, f64::max).max(1.0);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let min_exp = min.log10().floor() as i32;
+        #[allow(clippy::cast_possible_truncation)]
+        let max_exp = (max.log10().ceil() as i32).max(min_exp + 1);
+
+        Some(Self { min_exp, max_exp })
+    }
+
+    /// Position of `nanos` on the scale, between 0 and 1.
+    fn position(&self, nanos: f64) -> f64 {
+        let exp = nanos.max(1.0).log10();
+        ((exp - f64::from(self.min_exp)) / f64::from(self.max_exp - self.min_exp)).clamp(0.0, 1.0)
+    }
+
+    fn decades(&self) -> impl Iterator<Item = i32> {
+        self.min_exp..=self.max_exp
+    }
+}
+
+fn collect_rows(timings: &Timings) -> Vec<Row> {
+    timings
+        .data
+        .iter()
+        .map(|t| Row {
+            day: t.day.into_inner(),
+            parts: [t.part_nanos(1), t.part_nanos(2)],
+        })
+        .collect()
+}
+
+/// Formats a power of ten of nanoseconds, e.g. `100ns` or `10ms`.
+fn format_decade(exp: i32) -> String {
+    let units = ["ns", "µs", "ms", "s"];
+    let unit_index = usize::try_from(exp / 3).unwrap_or(0).min(units.len() - 1);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let value = 10_u64.pow((exp - 3 * unit_index as i32).unsigned_abs());
+    format!("{value}{}", units[unit_index])
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = std::time::Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+fn construct_ascii(rows: &[Row], scale: &LogScale) -> String {
+    let mut lines = vec![format!(
+        "log scale from {} to {}, `{}` part 1, `{}` part 2",
+        format_decade(scale.min_exp),
+        format_decade(scale.max_exp),
+        PART_SYMBOLS[0],
+        PART_SYMBOLS[1],
+    )];
+
+    for row in rows {
+        for (part, nanos) in row.parts.iter().enumerate() {
+            let label = if part == 0 {
+                format!("Day {:02}", row.day)
+            } else {
+                String::new()
+            };
+
+            let (bar, value) = match nanos {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(nanos) => {
+                    let len =
+                        ((scale.position(*nanos) * ASCII_WIDTH as f64).round() as usize).max(1);
+                    (
+                        PART_SYMBOLS[part].to_string().repeat(len),
+                        format_nanos(*nanos),
+                    )
+                }
+                None => (String::new(), "-".into()),
+            };
+
+            lines.push(format!("{label:<6} |{bar:<ASCII_WIDTH$}| {value}"));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn construct_svg(rows: &[Row], scale: &LogScale) -> String {
+    let day_height = 2.0 * SVG_BAR_HEIGHT + SVG_DAY_GAP;
+    #[allow(clippy::cast_precision_loss)]
+    let height = SVG_HEADER_HEIGHT + rows.len() as f64 * day_height + SVG_DAY_GAP;
+    let plot_width = SVG_WIDTH - SVG_LABEL_WIDTH - SVG_VALUE_WIDTH;
+    let x = |nanos: f64| SVG_LABEL_WIDTH + scale.position(nanos) * plot_width;
+
+    let mut svg = String::new();
+
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{height}" viewBox="0 0 {SVG_WIDTH} {height}" font-family="monospace" font-size="11">"#
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#0f0f23"/>"##
+    );
+
+    for (part, color) in SVG_COLORS.iter().enumerate() {
+        let legend_x = SVG_LABEL_WIDTH + 80.0 * part as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{legend_x}" y="6" width="10" height="10" fill="{color}"/><text x="{}" y="15" fill="{color}">Part {}</text>"#,
+            legend_x + 14.0,
+            part + 1
+        );
+    }
+
+    for exp in scale.decades() {
+        let gx = x(10_f64.powi(exp));
+        let _ = writeln!(
+            svg,
+            r##"<line x1="{gx:.1}" y1="{}" x2="{gx:.1}" y2="{height}" stroke="#333340"/><text x="{gx:.1}" y="{}" fill="#cccccc" text-anchor="middle">{}</text>"##,
+            SVG_HEADER_HEIGHT - 6.0,
+            SVG_HEADER_HEIGHT - 10.0,
+            format_decade(exp)
+        );
+    }
+
+    for (i, row) in rows.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let y = SVG_HEADER_HEIGHT + i as f64 * day_height;
+
+        let _ = writeln!(
+            svg,
+            r##"<text x="4" y="{:.1}" fill="#cccccc">Day {:02}</text>"##,
+            y + SVG_BAR_HEIGHT + 4.0,
+            row.day
+        );
+
+        for (part, nanos) in row.parts.iter().enumerate() {
+            let Some(nanos) = nanos else {
+                continue;
+            };
+
+            #[allow(clippy::cast_precision_loss)]
+            let bar_y = y + part as f64 * SVG_BAR_HEIGHT;
+            let bar_width = (x(*nanos) - SVG_LABEL_WIDTH).max(1.0);
+
+            let _ = writeln!(
+                svg,
+                r##"<rect x="{SVG_LABEL_WIDTH}" y="{bar_y:.1}" width="{bar_width:.1}" height="{}" fill="{}"/><text x="{:.1}" y="{:.1}" fill="#cccccc" font-size="9">{}</text>"##,
+                SVG_BAR_HEIGHT - 1.0,
+                SVG_COLORS[part],
+                SVG_LABEL_WIDTH + bar_width + 4.0,
+                bar_y + SVG_BAR_HEIGHT - 2.0,
+                format_nanos(*nanos)
+            );
+        }
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn construct_section(rows: &[Row], scale: &LogScale) -> String {
+    [
+        MARKER,
+        &format!("![Solution times on a log scale](./{SVG_PATH})"),
+        "",
+        "<details>",
+        "<summary>Text version</summary>",
+        "",
+        "```text",
+        &construct_ascii(rows, scale),
+        "```",
+        "",
+        "</details>",
+        MARKER,
+    ]
+    .join("\n")
+}
+
+/// Replaces the chart section of the readme, returns the SVG if there are times to plot.
+fn update_content(s: &mut String, timings: &Timings) -> Result<Option<String>, Error> {
+    let positions = locate_section(s, MARKER)?;
+    let rows = collect_rows(timings);
+
+    let Some(scale) = LogScale::new(&rows) else {
+        return Ok(None);
+    };
+
+    s.replace_range(
+        positions.pos_start..positions.pos_end,
+        &construct_section(&rows, &scale),
+    );
+
+    Ok(Some(construct_svg(&rows, &scale)))
+}
+
+pub fn update(timings: &Timings) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    if let Some(svg) = update_content(&mut readme, timings)? {
+        fs::create_dir_all(".assets")?;
+        fs::write(SVG_PATH, svg)?;
+        fs::write(path, &readme)?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MARKER, format_decade, update_content};
+    use crate::{
+        day,
+        template::timings::{PartStats, Timing, Timings},
+    };
+
+    fn get_mock_timings() -> Timings {
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(1),
+                    part_1: Some("2.0µs".into()),
+                    part_2: Some("20.0µs".into()),
+                    total_nanos: 22_000_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
+                },
+                Timing {
+                    day: day!(2),
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    total_nanos: 1_000_000_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(2), "100ns");
+        assert_eq!(format_decade(3), "1µs");
+        assert_eq!(format_decade(7), "10ms");
+        assert_eq!(format_decade(9), "1s");
+        assert_eq!(format_decade(11), "100s");
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_timings()).unwrap();
+    }
+
+    #[test]
+    fn skips_empty_timings() {
+        let mut s = format!("{MARKER}{MARKER}");
+        let svg = update_content(&mut s, &Timings::default()).unwrap();
+        assert!(svg.is_none());
+        assert_eq!(s, format!("{MARKER}{MARKER}"));
+    }
+
+    #[test]
+    fn updates_chart() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        let svg = update_content(&mut s, &get_mock_timings())
+            .unwrap()
+            .unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "![Solution times on a log scale](./.assets/benchmarks.svg)",
+            "",
+            "<details>",
+            "<summary>Text version</summary>",
+            "",
+            "```text",
+            "log scale from 1µs to 1ms, `#` part 1, `=` part 2",
+            "Day 01 |####                                    | 2.0µs",
+            "       |=================                       | 20.0µs",
+            "Day 02 |########################################| 1.0ms",
+            "       |                                        | -",
+            "```",
+            "",
+            "</details>",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("Day 0").count(), 2);
+        assert!(svg.contains(">1ms</text>"));
+    }
+}
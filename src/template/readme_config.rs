@@ -0,0 +1,273 @@
+/// Configuration of the benchmarks table that is written to the readme.
+/// Read from `data/readme.json`, every key is optional:
+///
+/// ```json
+/// {
+///     "heading_level": 2,
+///     "title": "Benchmarks",
+///     "columns": ["puzzle", "part_1", "part_2", "median", "samples", "memory", "parse", "stars"],
+///     "unit": "ms",
+///     "sort": "day",
+///     "part_totals": true
+/// }
+/// ```
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+static CONFIG_FILE_PATH: &str = "./data/readme.json";
+
+/// An optional column of the benchmarks table. The day column is always present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Part1,
+    Part2,
+    Median,
+    Samples,
+    Memory,
+    Parse,
+    Stars,
+    Puzzle,
+}
+
+impl Column {
+    pub fn header(self) -> &'static str {
+        match self {
+            Self::Part1 => "Part 1",
+            Self::Part2 => "Part 2",
+            Self::Median => "Median",
+            Self::Samples => "Samples",
+            Self::Memory => "Peak memory",
+            Self::Parse => "Parse",
+            Self::Stars => "Stars",
+            Self::Puzzle => "Puzzle",
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part_1" => Ok(Self::Part1),
+            "part_2" => Ok(Self::Part2),
+            "median" => Ok(Self::Median),
+            "samples" => Ok(Self::Samples),
+            "memory" => Ok(Self::Memory),
+            "parse" => Ok(Self::Parse),
+            "stars" => Ok(Self::Stars),
+            "puzzle" => Ok(Self::Puzzle),
+            other => Err(format!("unknown column `{other}`.")),
+        }
+    }
+}
+
+/// A unit that all durations in the table are converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeUnit {
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl TimeUnit {
+    /// Formats a duration given in nanoseconds in this unit.
+    pub fn format(self, nanos: f64) -> String {
+        match self {
+            Self::Nanos => format!("{nanos:.0}ns"),
+            Self::Micros => format!("{:.2}µs", nanos / 1_000_f64),
+            Self::Millis => format!("{:.2}ms", nanos / 1_000_000_f64),
+            Self::Seconds => format!("{:.2}s", nanos / 1_000_000_000_f64),
+        }
+    }
+}
+
+impl FromStr for TimeUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ns" => Ok(Self::Nanos),
+            "µs" | "us" => Ok(Self::Micros),
+            "ms" => Ok(Self::Millis),
+            "s" => Ok(Self::Seconds),
+            other => Err(format!("unknown unit `{other}`.")),
+        }
+    }
+}
+
+/// Order of the rows of the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Fastest days first.
+    Total,
+    /// Slowest days first.
+    TotalDesc,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "total" => Ok(Self::Total),
+            "total_desc" => Ok(Self::TotalDesc),
+            other => Err(format!("unknown sort order `{other}`.")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableConfig {
+    pub heading_level: usize,
+    pub title: String,
+    /// Columns after the day column. If not set, both parts are shown, plus peak memory if measured.
+    pub columns: Option<Vec<Column>>,
+    /// If not set, durations are shown as measured and the total in milliseconds.
+    pub unit: Option<TimeUnit>,
+    pub sort: SortOrder,
+    /// Whether to add a row with the sum of each part over all days.
+    pub part_totals: bool,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            heading_level: 2,
+            title: "Benchmarks".into(),
+            columns: None,
+            unit: None,
+            sort: SortOrder::Day,
+            part_totals: false,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Read the config from a JSON file. If not present, returns the default config.
+    pub fn read_from_file() -> Self {
+        let Ok(contents) = fs::read_to_string(CONFIG_FILE_PATH) else {
+            return Self::default();
+        };
+
+        Self::try_from(contents).unwrap_or_else(|e| {
+            eprintln!("Ignoring invalid {CONFIG_FILE_PATH}: {e}");
+            Self::default()
+        })
+    }
+}
+
+impl TryFrom<String> for TableConfig {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let get_str = |key: &str| -> Result<Option<&String>, String> {
+            json.get(key)
+                .map(|v| {
+                    v.get::<String>()
+                        .ok_or(format!("expected `{key}` to be a string."))
+                })
+                .transpose()
+        };
+
+        let mut config = Self::default();
+
+        if let Some(level) = json.get("heading_level") {
+            let level = level
+                .get::<f64>()
+                .filter(|l| (1.0..=6.0).contains(*l))
+                .ok_or("expected `heading_level` to be a number between 1 and 6.")?;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                config.heading_level = *level as usize;
+            }
+        }
+
+        if let Some(title) = get_str("title")? {
+            config.title.clone_from(title);
+        }
+
+        if let Some(columns) = json.get("columns") {
+            let columns = columns
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `columns` to be an array.")?
+                .iter()
+                .map(|c| {
+                    c.get::<String>()
+                        .ok_or_else(|| "expected `columns` to contain strings.".to_string())
+                        .and_then(|c| c.parse())
+                })
+                .collect::<Result<_, _>>()?;
+            config.columns = Some(columns);
+        }
+
+        if let Some(unit) = get_str("unit")? {
+            config.unit = Some(unit.parse()?);
+        }
+
+        if let Some(sort) = get_str("sort")? {
+            config.sort = sort.parse()?;
+        }
+
+        if let Some(part_totals) = json.get("part_totals") {
+            config.part_totals = *part_totals
+                .get::<bool>()
+                .ok_or("expected `part_totals` to be a boolean.")?;
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Column, SortOrder, TableConfig, TimeUnit};
+
+    #[test]
+    fn parses_empty_config() {
+        let config = TableConfig::try_from("{}".to_string()).unwrap();
+        assert_eq!(config, TableConfig::default());
+    }
+
+    #[test]
+    fn parses_full_config() {
+        let json = r#"{ "heading_level": 3, "title": "Timings", "columns": ["puzzle", "part_1", "stars"], "unit": "us", "sort": "total_desc", "part_totals": true }"#;
+        let config = TableConfig::try_from(json.to_string()).unwrap();
+        assert_eq!(config.heading_level, 3);
+        assert_eq!(config.title, "Timings");
+        assert_eq!(
+            config.columns,
+            Some(vec![Column::Puzzle, Column::Part1, Column::Stars])
+        );
+        assert_eq!(config.unit, Some(TimeUnit::Micros));
+        assert_eq!(config.sort, SortOrder::TotalDesc);
+        assert!(config.part_totals);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(TableConfig::try_from(r#"{ "columns": ["foo"] }"#.to_string()).is_err());
+        assert!(TableConfig::try_from(r#"{ "heading_level": 7 }"#.to_string()).is_err());
+        assert!(TableConfig::try_from(r#"{ "unit": 1 }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn formats_units() {
+        assert_eq!(TimeUnit::Nanos.format(1234.4), "1234ns");
+        assert_eq!(TimeUnit::Micros.format(1234.5), "1.23µs");
+        assert_eq!(TimeUnit::Millis.format(1_500_000.0), "1.50ms");
+        assert_eq!(TimeUnit::Seconds.format(2e9), "2.00s");
+    }
+}
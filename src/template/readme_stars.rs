@@ -0,0 +1,135 @@
+/// Module that renders the earned stars into the readme, laid out like the advent calendar.
+use std::fs;
+
+use crate::template::readme_benchmarks::{Error, locate_section};
+use crate::template::stars::{Stars, puzzle_count};
+use crate::template::{Day, aoc_cli};
+
+static MARKER: &str = "<!--- advent calendar --->";
+
+static WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Weekday of the 1st of december, `0` being monday.
+fn first_weekday(year: u16) -> usize {
+    // Sakamoto's method, which yields `0` for sunday.
+    let year = usize::from(year);
+    let sunday_based = (year + year / 4 - year / 100 + year / 400 + 4 + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+fn format_day(day: u8, stars: &Stars) -> String {
+    let count = Day::new(day).map_or(0, |day| stars.day(day));
+    if count == 0 {
+        day.to_string()
+    } else {
+        format!("{day} {}", "⭐".repeat(count.into()))
+    }
+}
+
+fn construct_calendar(stars: &Stars, year: Option<u16>) -> String {
+    let days = puzzle_count(year);
+    // NOTE: without a year, the calendar starts on a monday.
+    let offset = year.map_or(0, first_weekday);
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("| {} |", WEEKDAYS.join(" | ")),
+        format!("| {}  |", vec![":---:"; WEEKDAYS.len()].join(" | ")),
+    ];
+
+    let cells: Vec<String> = (0..offset)
+        .map(|_| String::new())
+        .chain((1..=days).map(|day| format_day(day, stars)))
+        .collect();
+
+    for week in cells.chunks(WEEKDAYS.len()) {
+        let mut week = week.to_vec();
+        week.resize(WEEKDAYS.len(), String::new());
+        lines.push(format!("| {} |", week.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "**Stars: {} / {}**",
+        stars.total(),
+        u32::from(days) * 2
+    ));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, stars: &Stars, year: Option<u16>) -> Result<(), Error> {
+    let positions = locate_section(s, MARKER)?;
+    let calendar = construct_calendar(stars, year);
+    s.replace_range(positions.pos_start..positions.pos_end, &calendar);
+    Ok(())
+}
+
+pub fn update(stars: &Stars) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, stars, aoc_cli::get_year())?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{MARKER, first_weekday, update_content};
+    use crate::{day, template::stars::Stars};
+
+    #[test]
+    fn computes_first_weekday() {
+        // 2025-12-01 is a monday, 2024-12-01 a sunday and 2023-12-01 a friday.
+        assert_eq!(first_weekday(2025), 0);
+        assert_eq!(first_weekday(2024), 6);
+        assert_eq!(first_weekday(2023), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &Stars::default(), Some(2025)).unwrap();
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let stars: Stars = [(day!(1), 2), (day!(2), 1), (day!(9), 2)]
+            .into_iter()
+            .collect();
+
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, &stars, Some(2025)).unwrap();
+        update_content(&mut s, &stars, Some(2025)).unwrap();
+
+        let expected = [
+            "foo",
+            MARKER,
+            "| Mon | Tue | Wed | Thu | Fri | Sat | Sun |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| 1 ⭐⭐ | 2 ⭐ | 3 | 4 | 5 | 6 | 7 |",
+            "| 8 | 9 ⭐⭐ | 10 | 11 | 12 |  |  |",
+            "",
+            "**Stars: 5 / 24**",
+            MARKER,
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn offsets_calendar_by_weekday() {
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, &Stars::default(), Some(2024)).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "|  |  |  |  |  |  | 1 |");
+        assert_eq!(lines[7], "| 23 | 24 | 25 |  |  |  |  |");
+        assert_eq!(lines[9], "**Stars: 0 / 50**");
+    }
+}
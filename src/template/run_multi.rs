@@ -7,7 +7,12 @@ use super::{
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_dhat: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -24,7 +29,7 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, is_timed, is_release, is_dhat).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
@@ -68,7 +73,11 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::{
+        Day,
+        runner::MemoryStats,
+        timings::{PartStats, parse_duration_nanos},
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -76,8 +85,14 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// With `is_dhat`, the bin is built with the dhat profile so its output contains memory stats.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_dhat: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -86,7 +101,9 @@ pub mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if is_dhat {
+            args.extend(["--profile", "dhat", "--features", "dhat-heap"]);
+        } else if is_release {
             args.push("--release");
         }
 
@@ -134,12 +151,18 @@ pub mod child_commands {
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            part_1_stats: PartStats::default(),
+            part_2_stats: PartStats::default(),
+            parse: None,
         };
 
+        timings.parse = output.iter().find_map(|l| parse_parse_time(l));
+
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                // NOTE: parsing happens again within the parts, so it is not part of the total.
+                if !l.contains(" samples)") || l.starts_with("Parse: ") {
                     return None;
                 }
 
@@ -149,13 +172,15 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_stats(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, stats)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_stats = stats;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_stats = stats;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,12 +189,52 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parse the benched time of the day's parser, e.g. from `Parse: 1.2 KiB input (12.3µs @ 100 samples)`.
+    fn parse_parse_time(line: &str) -> Option<String> {
+        let timing = line
+            .strip_prefix("Parse: ")?
+            .rsplit_once('(')?
+            .1
+            .strip_suffix(" samples)")?
+            .split_once(" @ ")?
+            .0;
+        parse_duration_nanos(timing)?;
+        Some(timing.into())
+    }
+
+    /// Parse the sample count and the annotations that are appended to a benched timing,
+    /// e.g. `(1ms @ 10 samples) [median 0.9ms; 2.0 KiB peak, 3 allocs]`.
+    fn parse_stats(line: &str) -> PartStats {
+        let (measurement, annotations) = line
+            .rsplit_once(" samples)")
+            .map_or((line, ""), |(m, a)| (m, a.trim()));
+
+        let samples = measurement
+            .rsplit_once(" @ ")
+            .and_then(|(_, samples)| samples.parse().ok());
+
+        let mut stats = PartStats {
+            samples,
+            ..PartStats::default()
+        };
+
+        let annotations = annotations
+            .strip_prefix('[')
+            .and_then(|a| a.strip_suffix(']'))
+            .unwrap_or_default();
+
+        for annotation in annotations.split("; ") {
+            if let Some(median) = annotation.strip_prefix("median ") {
+                stats.median = Some(median.into());
+            } else if let Ok(memory) = annotation.parse::<MemoryStats>() {
+                stats.memory = Some(memory);
+            }
+        }
+
+        stats
     }
 
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
@@ -179,14 +244,7 @@ pub mod child_commands {
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        Some((str_timing, parse_duration_nanos(str_timing)?))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -253,5 +311,44 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_memory_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [1.5 KiB peak, 12 allocs]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            let memory = res.part_1_stats.memory.unwrap();
+            assert_eq!(memory.allocations, 12);
+            assert_eq!(memory.peak_bytes, 1536);
+            assert_eq!(res.part_2_stats.memory.is_none(), true);
+        }
+
+        #[test]
+        fn parses_bench_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: 19.5 KiB input (12.3µs @ 1000 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples) [median 70.1ns; 1.5 KiB peak, 12 allocs]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) [median 70.2ms]".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.parse.unwrap(), "12.3µs");
+            assert_eq!(res.part_1_stats.samples, Some(100000));
+            assert_eq!(res.part_1_stats.median.unwrap(), "70.1ns");
+            assert_eq!(res.part_1_stats.memory.unwrap().allocations, 12);
+            assert_eq!(res.part_2_stats.samples, Some(99999));
+            assert_eq!(res.part_2_stats.median.unwrap(), "70.2ms");
+            assert_eq!(res.part_2_stats.memory.is_none(), true);
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+        }
     }
 }
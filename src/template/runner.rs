@@ -3,54 +3,162 @@ use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
 use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::{
+    ANSI_ITALIC, ANSI_RESET, Day, InputMode, aoc_cli, read_file_normalized, stars,
+};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, measurements) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &measurements.to_string());
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        let answer = result.to_string();
+        if let Some(Ok(output)) = submit_result(result, day, part)
+            && aoc_cli::is_correct_answer(&output)
+        {
+            match stars::record_answer(day, part, &answer) {
+                Ok(()) => println!("Recorded answer of part {part} in data/answers.json."),
+                Err(e) => eprintln!("Failed to record answer: {e}"),
+            }
+        }
+    }
+}
+
+/// Reads and normalizes the input for `day`.
+pub fn read_input(day: Day, mode: InputMode) -> String {
+    read_file_normalized("inputs", day, mode)
+}
+
+/// Benches the parser of a day when running with `--time`, and prints its time as the parse time of the day.
+pub fn run_parse<T>(func: impl Fn(&str) -> T, input: &str) {
+    if !env::args().any(|x| x == "--time") {
+        return;
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    let size = format_bytes(input.len() as u64);
+    print!("Parse: {size} input");
+
+    let timer = Instant::now();
+    black_box(func(black_box(input)));
+    let base_time = timer.elapsed();
+
+    let (duration, samples, _) = bench(func, input, &base_time);
+    println!("\rParse: {size} input ({duration:.1?} @ {samples} samples)");
+}
+
+/// Prints the report of a day when running with `--report`.
+/// It goes to stderr, so it doesn't mix with the answers and timings that are parsed from stdout.
+pub fn run_report(func: impl Fn(&str) -> String, input: &str) {
+    if env::args().any(|x| x == "--report") {
+        eprint!("{}", func(input));
+    }
+}
+
+/// Measurements that were taken while running a solution part.
+///
+/// # Display
+/// This value displays as ` (<average> @ <samples> samples) [median <median>; <memory>]`.
+/// Samples are omitted for single runs, and the bracketed annotations if there are none.
+struct Measurements {
+    duration: Duration,
+    samples: u128,
+    median: Option<Duration>,
+    memory: Option<MemoryStats>,
+}
+
+impl Display for Measurements {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.samples == 1 {
+            write!(f, " ({:.1?})", self.duration)?;
+        } else {
+            write!(f, " ({:.1?} @ {} samples)", self.duration, self.samples)?;
+        }
+
+        let mut annotations = vec![];
+
+        if let Some(median) = self.median {
+            annotations.push(format!("median {median:.1?}"));
+        }
+
+        if let Some(memory) = self.memory {
+            annotations.push(memory.to_string());
+        }
+
+        if !annotations.is_empty() {
+            write!(f, " [{}]", annotations.join("; "))?;
+        }
+
+        Ok(())
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+///
+/// When the `dhat-heap` feature is enabled, the heap usage of the first execution is measured as well.
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Measurements) {
+    #[cfg(feature = "dhat-heap")]
+    let profiler = dhat::Profiler::new_heap();
 
-        func(input)
-    };
+    let timer = Instant::now();
+    let result = func(input);
     let base_time = timer.elapsed();
 
+    let memory = heap_stats();
+
+    #[cfg(feature = "dhat-heap")]
+    drop(profiler);
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, samples, median) = if std::env::args().any(|x| x == "--time") {
+        let (duration, samples, median) = bench(func, input, &base_time);
+        (duration, samples, Some(median))
     } else {
-        (base_time, 1)
+        (base_time, 1, None)
     };
 
-    (result, run.0, run.1)
+    let measurements = Measurements {
+        duration,
+        samples,
+        median,
+        memory,
+    };
+
+    (result, measurements)
+}
+
+#[cfg(feature = "dhat-heap")]
+fn heap_stats() -> Option<MemoryStats> {
+    let stats = dhat::HeapStats::get();
+    Some(MemoryStats {
+        allocations: stats.total_blocks,
+        peak_bytes: stats.max_bytes as u64,
+    })
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_stats() -> Option<MemoryStats> {
+    None
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+/// Returns the average duration, the number of samples and the median duration.
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Duration) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
@@ -59,9 +167,19 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    let (average, median) = sample(func, input, bench_iterations);
+    (average, bench_iterations, median)
+}
+
+/// Runs `func` `iterations` times and returns the average and the median duration.
+pub(crate) fn sample<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    iterations: u128,
+) -> (Duration, Duration) {
     let mut timers: Vec<Duration> = vec![];
 
-    for _ in 0..bench_iterations {
+    for _ in 0..iterations {
         let timer = Instant::now();
         black_box(func(black_box(input)));
         timers.push(timer.elapsed());
@@ -70,7 +188,7 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
     (
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+        median_duration(&mut timers),
     )
 }
 
@@ -82,11 +200,14 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn median_duration(numbers: &mut [Duration]) -> Duration {
+    numbers.sort_unstable();
+    let mid = numbers.len() / 2;
+
+    if numbers.len().is_multiple_of(2) {
+        (numbers[mid - 1] + numbers[mid]) / 2
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        numbers[mid]
     }
 }
 
@@ -165,3 +286,166 @@ fn submit_result<T: Display>(
     println!("Submitting result via aoc-cli...");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
+
+/// Heap usage of a single execution of a solution part, as reported by dhat.
+///
+/// # Display
+/// This value displays as `<peak> peak, <allocations> allocs`, with the peak in binary units.
+/// Parsing the displayed value rounds the peak to the displayed precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of allocations over the entire execution.
+    pub allocations: u64,
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+const BYTE_UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} allocs",
+            format_bytes(self.peak_bytes),
+            self.allocations
+        )
+    }
+}
+
+impl FromStr for MemoryStats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (peak, allocations) = s
+            .split_once(", ")
+            .ok_or_else(|| format!("expected `<peak> peak, <n> allocs`, got `{s}`."))?;
+
+        let peak_bytes = peak
+            .strip_suffix(" peak")
+            .and_then(parse_bytes)
+            .ok_or_else(|| format!("could not parse peak memory from `{peak}`."))?;
+
+        let allocations = allocations
+            .strip_suffix(" allocs")
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| format!("could not parse allocations from `{allocations}`."))?;
+
+        Ok(Self {
+            allocations,
+            peak_bytes,
+        })
+    }
+}
+
+/// Formats a byte count with binary units, e.g. `512 B` or `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = BYTE_UNITS[0];
+
+    for next_unit in &BYTE_UNITS[1..] {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{value:.1} {unit}")
+}
+
+fn parse_bytes(s: &str) -> Option<u64> {
+    let (value, unit) = s.split_once(' ')?;
+
+    if unit == "B" {
+        return value.parse().ok();
+    }
+
+    let exponent = BYTE_UNITS.iter().position(|u| *u == unit)?;
+    let value = value.parse::<f64>().ok()? * 1024_f64.powi(i32::try_from(exponent).ok()? + 1);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some(value.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Measurements, MemoryStats, format_bytes, median_duration};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn computes_median_duration() {
+        let mut odd = [3, 1, 2].map(Duration::from_nanos);
+        assert_eq!(median_duration(&mut odd), Duration::from_nanos(2));
+
+        let mut even = [4, 1, 3, 2].map(Duration::from_nanos);
+        assert_eq!(median_duration(&mut even), Duration::from_nanos(2));
+    }
+
+    #[test]
+    fn displays_measurements() {
+        let single = Measurements {
+            duration: Duration::from_micros(12),
+            samples: 1,
+            median: None,
+            memory: None,
+        };
+        assert_eq!(single.to_string(), " (12.0µs)");
+
+        let benched = Measurements {
+            duration: Duration::from_micros(12),
+            samples: 100,
+            median: Some(Duration::from_micros(11)),
+            memory: Some(MemoryStats {
+                allocations: 3,
+                peak_bytes: 2048,
+            }),
+        };
+        assert_eq!(
+            benched.to_string(),
+            " (12.0µs @ 100 samples) [median 11.0µs; 2.0 KiB peak, 3 allocs]"
+        );
+    }
+
+    #[test]
+    fn displays_memory_stats() {
+        let stats = MemoryStats {
+            allocations: 42,
+            peak_bytes: 2048,
+        };
+        assert_eq!(stats.to_string(), "2.0 KiB peak, 42 allocs");
+    }
+
+    #[test]
+    fn parses_memory_stats() {
+        let stats: MemoryStats = "1.5 KiB peak, 42 allocs".parse().unwrap();
+        assert_eq!(stats.allocations, 42);
+        assert_eq!(stats.peak_bytes, 1536);
+
+        let stats: MemoryStats = "100 B peak, 0 allocs".parse().unwrap();
+        assert_eq!(stats.peak_bytes, 100);
+    }
+
+    #[test]
+    fn rejects_malformed_memory_stats() {
+        assert!("1.5 KiB, 42 allocs".parse::<MemoryStats>().is_err());
+        assert!("1.5 XB peak, 42 allocs".parse::<MemoryStats>().is_err());
+    }
+}
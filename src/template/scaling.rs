@@ -0,0 +1,435 @@
+//! Runs solutions on generated inputs of growing size, to see how their run time scales.
+//!
+//! Started with `cargo time NN --scale`, which runs the solution binary with `--scale`.
+//! The measured times are fitted to common complexity classes, where `n` is the size of the input in bytes.
+
+use std::fmt::{self, Display, Write};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use std::{cmp, process};
+
+use crate::rng::Rng;
+use crate::template::fuzz::{PartFn, exit_with_error};
+use crate::template::generator::{GenerateFn, generate_input};
+use crate::template::runner::{format_bytes, sample};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, InputMode};
+
+const DEFAULT_MAX_SIZE: usize = 1 << 20;
+
+/// Generated inputs stop growing once they are larger than this.
+const MAX_INPUT_BYTES: usize = 64 << 20;
+
+/// A part is no longer run on larger inputs once a single run takes longer than this.
+const TIME_LIMIT: Duration = Duration::from_millis(500);
+
+/// Approximate time spent on repeated runs of a part for each input.
+const SAMPLE_BUDGET: Duration = Duration::from_millis(100);
+
+const PLOT_WIDTH: usize = 60;
+const PLOT_HEIGHT: usize = 16;
+const PART_SYMBOLS: [char; 2] = ['1', '2'];
+
+/// Options of a scaling run, read from the arguments of the solution binary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaleOptions {
+    /// Largest size that is passed to the generator, sizes double from 1 up to it.
+    pub max_size: usize,
+    pub seed: u64,
+}
+
+impl ScaleOptions {
+    /// Returns the options if the binary was started with `--scale`.
+    pub fn from_args() -> Option<Self> {
+        let mut args = pico_args::Arguments::from_env();
+        if !args.contains("--scale") {
+            return None;
+        }
+
+        let max_size = args
+            .opt_value_from_str("--size")
+            .unwrap_or_else(exit_with_error);
+        let seed = args
+            .opt_value_from_str("--seed")
+            .unwrap_or_else(exit_with_error);
+
+        Some(Self {
+            max_size: max_size.unwrap_or(DEFAULT_MAX_SIZE),
+            seed: seed.unwrap_or_else(|| Rng::from_time().next_u64()),
+        })
+    }
+}
+
+/// Median run time of a part on an input of `bytes` bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Sample {
+    part: u8,
+    bytes: usize,
+    median: Duration,
+}
+
+/// Complexity classes that run times are fitted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Complexity {
+    const ALL: [Self; 6] = [
+        Self::Constant,
+        Self::Logarithmic,
+        Self::Linear,
+        Self::Linearithmic,
+        Self::Quadratic,
+        Self::Cubic,
+    ];
+
+    /// Natural logarithm of the cost of an input of size `n`, up to a constant factor.
+    fn ln_cost(self, n: f64) -> f64 {
+        let ln_n = n.max(1.0).ln();
+        let ln_log_n = n.max(2.0).log2().ln();
+
+        match self {
+            Self::Constant => 0.0,
+            Self::Logarithmic => ln_log_n,
+            Self::Linear => ln_n,
+            Self::Linearithmic => ln_n + ln_log_n,
+            Self::Quadratic => 2.0 * ln_n,
+            Self::Cubic => 3.0 * ln_n,
+        }
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Self::Constant => "O(1)",
+            Self::Logarithmic => "O(log n)",
+            Self::Linear => "O(n)",
+            Self::Linearithmic => "O(n log n)",
+            Self::Quadratic => "O(n²)",
+            Self::Cubic => "O(n³)",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// An empirical complexity curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    /// The complexity class whose shape matches the run times best.
+    pub complexity: Complexity,
+    /// Exponent `k` of the power law `time ~ n^k` that fits the run times best.
+    pub exponent: f64,
+}
+
+/// Fits `(n, nanos)` measurements to a complexity class and a power law on a log-log scale.
+/// Returns [`None`] if there are fewer than three distinct sizes.
+#[must_use]
+pub fn fit(points: &[(f64, f64)]) -> Option<Fit> {
+    let mut points: Vec<(f64, f64)> = points
+        .iter()
+        .map(|&(n, nanos)| (n.max(1.0), nanos.max(1.0)))
+        .collect();
+    points.sort_unstable_by(|a, b| a.0.total_cmp(&b.0));
+    points.dedup_by(|a, b| a.0 == b.0);
+
+    // NOTE: fixed overhead dominates small inputs, so only the larger half of them is fitted.
+    let take = cmp::max(3, points.len().div_ceil(2));
+    let points = &points[points.len().checked_sub(take)?..];
+
+    #[allow(clippy::cast_precision_loss)]
+    let count = points.len() as f64;
+    let mean = |values: &mut dyn Iterator<Item = f64>| values.sum::<f64>() / count;
+
+    let mean_x = mean(&mut points.iter().map(|(n, _)| n.ln()));
+    let mean_y = mean(&mut points.iter().map(|(_, t)| t.ln()));
+    let covariance = mean(
+        &mut points
+            .iter()
+            .map(|(n, t)| (n.ln() - mean_x) * (t.ln() - mean_y)),
+    );
+    let variance = mean(&mut points.iter().map(|(n, _)| (n.ln() - mean_x).powi(2)));
+
+    // the best class is the one whose cost is closest to a constant multiple of the times.
+    let spread = |complexity: Complexity| {
+        let ratios: Vec<f64> = points
+            .iter()
+            .map(|&(n, t)| t.ln() - complexity.ln_cost(n))
+            .collect();
+        let mean_ratio = mean(&mut ratios.iter().copied());
+        mean(&mut ratios.iter().map(|r| (r - mean_ratio).powi(2)))
+    };
+
+    let complexity = Complexity::ALL
+        .into_iter()
+        .min_by(|a, b| spread(*a).total_cmp(&spread(*b)))?;
+
+    Some(Fit {
+        complexity,
+        exponent: covariance / variance,
+    })
+}
+
+/// Runs all `parts` on generated inputs of doubling size, then prints their fitted complexity and a plot of the times.
+pub fn run(
+    day: Day,
+    mode: InputMode,
+    generator: Option<GenerateFn>,
+    parts: &[(u8, PartFn)],
+    options: &ScaleOptions,
+) {
+    let Some(generator) = generator else {
+        eprintln!(
+            "Day {day} has no input generator. Add one with `solution!({}, generator = ...)`.",
+            day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    println!(
+        "Scaling day {day} up to size {} (seed {}).\n",
+        options.max_size, options.seed
+    );
+
+    let mut header = format!("{:>8} | {:>10}", "size", "input");
+    for (part, _) in parts {
+        header.push_str(&format!(" | {:>10}", format!("Part {part}")));
+    }
+    println!("{ANSI_BOLD}{header}{ANSI_RESET}");
+
+    let mut rng = Rng::new(options.seed);
+    let mut samples = vec![];
+    let mut running = vec![true; parts.len()];
+    let mut size = 1;
+
+    while size <= options.max_size && running.contains(&true) {
+        let input = generate_input(generator, &mut rng, size, mode);
+        if input.len() > MAX_INPUT_BYTES {
+            break;
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let mut row = format!("{size:>8} | {:>10}", format_bytes(input.len() as u64));
+
+        for ((part, solve), running) in parts.iter().zip(&mut running) {
+            if !*running {
+                row.push_str(&format!(" | {:>10}", "-"));
+                continue;
+            }
+
+            let timer = Instant::now();
+            black_box(solve(black_box(&input)));
+            let base_time = timer.elapsed();
+
+            let median = if base_time > SAMPLE_BUDGET {
+                base_time
+            } else {
+                let iterations =
+                    (SAMPLE_BUDGET.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(1, 1000);
+                sample(solve, input.as_str(), iterations).1
+            };
+
+            *running = base_time <= TIME_LIMIT;
+            samples.push(Sample {
+                part: *part,
+                bytes: input.len(),
+                median,
+            });
+            row.push_str(&format!(" | {:>10}", format!("{median:.1?}")));
+        }
+
+        println!("{row}");
+        size *= 2;
+    }
+
+    println!();
+
+    for (part, _) in parts {
+        #[allow(clippy::cast_precision_loss)]
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .filter(|s| s.part == *part)
+            .map(|s| (s.bytes as f64, s.median.as_nanos() as f64))
+            .collect();
+
+        match fit(&points) {
+            Some(Fit {
+                complexity,
+                exponent,
+            }) => println!(
+                "{ANSI_BOLD}Part {part}{ANSI_RESET}: {complexity} {ANSI_ITALIC}(time ~ n^{exponent:.2}){ANSI_RESET}"
+            ),
+            None => println!(
+                "{ANSI_BOLD}Part {part}{ANSI_RESET}: not enough input sizes to fit a curve."
+            ),
+        }
+    }
+
+    if let Some(plot) = plot(&samples) {
+        println!("\n{plot}");
+    }
+}
+
+/// Plots the times of all samples against their input size, with logarithmic axes.
+fn plot(samples: &[Sample]) -> Option<String> {
+    #[allow(clippy::cast_precision_loss)]
+    let x = |s: &Sample| (s.bytes as f64).max(1.0).ln();
+    #[allow(clippy::cast_precision_loss)]
+    let y = |s: &Sample| (s.median.as_nanos() as f64).max(1.0).ln();
+
+    let min_by = |f: &dyn Fn(&Sample) -> f64| samples.iter().map(f).reduce(f64::min);
+    let max_by = |f: &dyn Fn(&Sample) -> f64| samples.iter().map(f).reduce(f64::max);
+    let (min_x, max_x) = (min_by(&x)?, max_by(&x)?);
+    let (min_y, max_y) = (min_by(&y)?, max_by(&y)?);
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let cell = |value: f64, min: f64, max: f64, cells: usize| {
+        if max > min {
+            ((value - min) / (max - min) * (cells - 1) as f64).round() as usize
+        } else {
+            0
+        }
+    };
+
+    let mut rows = vec![vec![' '; PLOT_WIDTH]; PLOT_HEIGHT];
+
+    for s in samples {
+        let col = cell(x(s), min_x, max_x, PLOT_WIDTH);
+        let row = PLOT_HEIGHT - 1 - cell(y(s), min_y, max_y, PLOT_HEIGHT);
+        let symbol = PART_SYMBOLS
+            .get(usize::from(s.part).wrapping_sub(1))
+            .copied()
+            .unwrap_or('?');
+
+        let c = &mut rows[row][col];
+        *c = if *c == ' ' || *c == symbol {
+            symbol
+        } else {
+            '*'
+        };
+    }
+
+    let shortest = samples.iter().map(|s| s.median).min()?;
+    let longest = samples.iter().map(|s| s.median).max()?;
+    let (top, bottom) = (format!("{longest:.1?}"), format!("{shortest:.1?}"));
+    let label_width = top.chars().count().max(bottom.chars().count());
+
+    let mut out = String::new();
+
+    for (i, row) in rows.iter().enumerate() {
+        let label = match i {
+            0 => top.as_str(),
+            i if i == PLOT_HEIGHT - 1 => bottom.as_str(),
+            _ => "",
+        };
+        let row: String = row.iter().collect();
+        let _ = writeln!(out, "{label:>label_width$} |{}", row.trim_end());
+    }
+
+    let smallest = samples.iter().map(|s| s.bytes).min()?;
+    let largest = samples.iter().map(|s| s.bytes).max()?;
+    #[allow(clippy::cast_possible_truncation)]
+    let (left, right) = (format_bytes(smallest as u64), format_bytes(largest as u64));
+
+    let _ = writeln!(out, "{:>label_width$} +{}", "", "-".repeat(PLOT_WIDTH));
+    let _ = write!(
+        out,
+        "{:>label_width$}  {left}{right:>width$}",
+        "",
+        width = PLOT_WIDTH.saturating_sub(left.len())
+    );
+
+    Some(out)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Complexity, PLOT_HEIGHT, Sample, fit, plot};
+
+    fn points(cost: impl Fn(f64) -> f64) -> Vec<(f64, f64)> {
+        (4..16)
+            .map(|exp| {
+                let n = f64::from(1 << exp);
+                (n, 250.0 * cost(n))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let cases: [(Complexity, &dyn Fn(f64) -> f64); 5] = [
+            (Complexity::Constant, &|_| 1.0),
+            (Complexity::Logarithmic, &f64::log2),
+            (Complexity::Linear, &|n| n),
+            (Complexity::Linearithmic, &|n| n * n.log2()),
+            (Complexity::Quadratic, &|n| n * n),
+        ];
+
+        for (complexity, cost) in cases {
+            let result = fit(&points(cost)).unwrap();
+            assert_eq!(result.complexity, complexity);
+        }
+
+        let quadratic = fit(&points(|n| n * n)).unwrap();
+        assert!((quadratic.exponent - 2.0).abs() < 1e-9);
+        assert_eq!(Complexity::Linearithmic.to_string(), "O(n log n)");
+    }
+
+    #[test]
+    fn ignores_overhead_of_small_inputs() {
+        let result = fit(&points(|n| 1000.0 + n)).unwrap();
+        assert_eq!(result.complexity, Complexity::Linear);
+    }
+
+    #[test]
+    fn needs_three_sizes() {
+        assert_eq!(fit(&[(1.0, 10.0), (2.0, 20.0)]), None);
+        assert_eq!(fit(&[(1.0, 10.0), (1.0, 20.0), (2.0, 40.0)]), None);
+    }
+
+    #[test]
+    fn plots_samples() {
+        let samples: Vec<Sample> = (0..4_u32)
+            .flat_map(|exp| {
+                let bytes = 16 << exp;
+                [
+                    Sample {
+                        part: 1,
+                        bytes,
+                        median: Duration::from_nanos(100 << exp),
+                    },
+                    Sample {
+                        part: 2,
+                        bytes,
+                        median: Duration::from_nanos(100 << (2 * exp)),
+                    },
+                ]
+            })
+            .collect();
+
+        let chart = plot(&samples).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines.len(), PLOT_HEIGHT + 2);
+        assert!(lines[0].trim_start().starts_with("6.4µs |"));
+        assert!(lines[0].trim_end().ends_with('2'));
+        assert!(lines[PLOT_HEIGHT - 1].starts_with("100.0ns |*"));
+        assert!(lines[PLOT_HEIGHT + 1].trim().starts_with("16 B"));
+        assert!(lines[PLOT_HEIGHT + 1].ends_with("128 B"));
+        assert!(chart.contains('1'));
+        assert_eq!(plot(&[]), None);
+    }
+}
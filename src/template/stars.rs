@@ -0,0 +1,318 @@
+/// Tracks earned stars locally, without access to the leaderboard.
+/// Stars are derived from answers that were accepted when submitting them with `--submit`,
+/// and from the answers that adventofcode.com shows in downloaded puzzle descriptions.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, all_days};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Starting with 2025, advent of code has 12 puzzles instead of 25.
+const SHORT_CALENDAR_SINCE: u16 = 2025;
+
+/// Represents the accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers of all days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns no answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records the accepted answer of a part, overwriting a previous answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            2 => entry.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Records an answer that was accepted on submission in the answers file.
+pub fn record_answer(day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file();
+    answers.record(day, part, answer);
+    answers.store_file()
+}
+
+/// Number of stars earned per day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stars {
+    days: HashMap<Day, u8>,
+}
+
+impl Stars {
+    /// Reads stars from the answers file and the downloaded puzzle descriptions.
+    pub fn read() -> Self {
+        let descriptions: Vec<(Day, String)> = all_days()
+            .filter_map(|day| {
+                let description = fs::read_to_string(format!("./data/puzzles/{day}.md")).ok()?;
+                Some((day, description))
+            })
+            .collect();
+
+        Self::from_sources(
+            &Answers::read_from_file(),
+            descriptions.iter().map(|(day, d)| (*day, d.as_str())),
+        )
+    }
+
+    /// Combines both sources of stars, a day has the stars of the source that knows about more of them.
+    pub fn from_sources<'a>(
+        answers: &Answers,
+        descriptions: impl Iterator<Item = (Day, &'a str)>,
+    ) -> Self {
+        let mut days: HashMap<Day, u8> = HashMap::new();
+
+        for answer in &answers.data {
+            let count = u8::from(answer.part_1.is_some()) + u8::from(answer.part_2.is_some());
+            days.insert(answer.day, count);
+        }
+
+        for (day, description) in descriptions {
+            let count = count_answers(description);
+            let entry = days.entry(day).or_default();
+            *entry = (*entry).max(count);
+        }
+
+        days.retain(|_, count| *count > 0);
+        Self { days }
+    }
+
+    /// Stars earned on `day`, between 0 and 2.
+    pub fn day(&self, day: Day) -> u8 {
+        self.days.get(&day).copied().unwrap_or_default()
+    }
+
+    /// Stars earned over all days.
+    pub fn total(&self) -> u32 {
+        self.days.values().map(|count| u32::from(*count)).sum()
+    }
+}
+
+impl FromIterator<(Day, u8)> for Stars {
+    fn from_iter<I: IntoIterator<Item = (Day, u8)>>(iter: I) -> Self {
+        Self {
+            days: iter.into_iter().filter(|(_, count)| *count > 0).collect(),
+        }
+    }
+}
+
+/// Counts the answers shown in a puzzle description, e.g. `Your puzzle answer was 1234.`.
+pub fn count_answers(description: &str) -> u8 {
+    let count = description
+        .lines()
+        .filter(|line| line.contains("Your puzzle answer was"))
+        .count();
+    u8::try_from(count.min(2)).unwrap_or(2)
+}
+
+/// Number of puzzles of an event. Without a year, the current format is assumed.
+pub fn puzzle_count(year: Option<u16>) -> u8 {
+    match year {
+        Some(year) if year < SHORT_CALENDAR_SINCE => 25,
+        _ => 12,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                answer.clone().map_or(JsonValue::Null, JsonValue::String),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Stars, count_answers, puzzle_count};
+    use crate::day;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(3), 1, "17");
+        answers.record(day!(1), 2, "42");
+        answers.record(day!(3), 2, "18");
+        answers.record(day!(3), 1, "19");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.data[0].part_1, None);
+        assert_eq!(answers.data[0].part_2.as_deref(), Some("42"));
+        assert_eq!(answers.data[1].part_1.as_deref(), Some("19"));
+        assert_eq!(answers.data[1].part_2.as_deref(), Some("18"));
+    }
+
+    #[test]
+    fn serializes_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "42");
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn counts_answers_in_description() {
+        let description = "## --- Day 1: Secret Entrance ---\n...\nYour puzzle answer was `1034`.\n\n## --- Part Two ---\n...\nYour puzzle answer was `6166`.";
+        assert_eq!(count_answers(description), 2);
+        assert_eq!(count_answers("## --- Day 1: Secret Entrance ---"), 0);
+    }
+
+    #[test]
+    fn combines_sources() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "42");
+        answers.record(day!(2), 1, "17");
+        answers.record(day!(2), 2, "18");
+
+        let stars = Stars::from_sources(
+            &answers,
+            [
+                (
+                    day!(1),
+                    "Your puzzle answer was `42`.\nYour puzzle answer was `43`.",
+                ),
+                (day!(2), "Your puzzle answer was `17`."),
+                (day!(3), "No answers yet."),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(stars.day(day!(1)), 2);
+        assert_eq!(stars.day(day!(2)), 2);
+        assert_eq!(stars.day(day!(3)), 0);
+        assert_eq!(stars.total(), 4);
+    }
+
+    #[test]
+    fn counts_puzzles() {
+        assert_eq!(puzzle_count(Some(2024)), 25);
+        assert_eq!(puzzle_count(Some(2025)), 12);
+        assert_eq!(puzzle_count(None), 12);
+    }
+}
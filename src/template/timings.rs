@@ -2,6 +2,7 @@ use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
+use crate::template::runner::MemoryStats;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -12,6 +13,18 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub part_1_stats: PartStats,
+    pub part_2_stats: PartStats,
+    /// Benched time of the day's `parse =` hook, or [`None`] for days without one.
+    pub parse: Option<String>,
+}
+
+/// Represents measurements for a single part that were taken besides its run time.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartStats {
+    pub memory: Option<MemoryStats>,
+    pub median: Option<String>,
+    pub samples: Option<u64>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +64,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -65,6 +78,49 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether any of the timings carries memory measurements.
+    pub fn has_memory_stats(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_stats.memory.is_some() || t.part_2_stats.memory.is_some())
+    }
+}
+
+impl Timing {
+    /// Time of a single part in nanoseconds, parsed from its formatted duration.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        let timing = match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }?;
+        parse_duration_nanos(timing)
+    }
+
+    /// Stats of a single part.
+    pub fn part_stats(&self, part: u8) -> Option<&PartStats> {
+        match part {
+            1 => Some(&self.part_1_stats),
+            2 => Some(&self.part_2_stats),
+            _ => None,
+        }
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a duration formatted with `{:?}` (e.g. `74.13µs`) into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -133,6 +189,13 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert("part_1_stats".into(), JsonValue::from(&value.part_1_stats));
+        map.insert("part_2_stats".into(), JsonValue::from(&value.part_2_stats));
+
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,11 +229,94 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: stats were added later, timings stored by older versions don't have them.
+        let part_1_stats = json
+            .get("part_1_stats")
+            .map(PartStats::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let part_2_stats = json
+            .get("part_2_stats")
+            .map(PartStats::try_from)
+            .transpose()?
+            .unwrap_or_default();
+
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            part_1_stats,
+            part_2_stats,
+            parse,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartStats> for JsonValue {
+    fn from(value: &PartStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        if let Some(memory) = value.memory {
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(memory.allocations as f64),
+            );
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(memory.peak_bytes as f64),
+            );
+        }
+
+        if let Some(median) = &value.median {
+            map.insert("median".into(), JsonValue::String(median.clone()));
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        if let Some(samples) = value.samples {
+            map.insert("samples".into(), JsonValue::Number(samples as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get_count = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+        };
+
+        let memory = match (get_count("allocations"), get_count("peak_bytes")) {
+            (Some(allocations), Some(peak_bytes)) => Some(MemoryStats {
+                allocations,
+                peak_bytes,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err("Expected stats to have both allocations and peak_bytes.".into());
+            }
+        };
+
+        Ok(PartStats {
+            memory,
+            median: json.get("median").and_then(|v| v.get::<String>()).cloned(),
+            samples: get_count("samples"),
         })
     }
 }
@@ -179,9 +325,9 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{day, template::runner::MemoryStats};
 
-    use super::{Timing, Timings};
+    use super::{PartStats, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -191,18 +337,34 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    part_1_stats: PartStats {
+                        memory: Some(MemoryStats {
+                            allocations: 12,
+                            peak_bytes: 2048,
+                        }),
+                        median: Some("39ms".into()),
+                        samples: Some(10),
+                    },
+                    part_2_stats: PartStats::default(),
+                    parse: Some("12µs".into()),
                 },
             ],
         }
@@ -221,6 +383,18 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats.memory, None);
+        }
+
+        #[test]
+        fn handles_json_part_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "part_1_stats": { "allocations": 3, "peak_bytes": 1024 }, "part_2_stats": {} }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let memory = timing.part_1_stats.memory.unwrap();
+            assert_eq!(memory.allocations, 3);
+            assert_eq!(memory.peak_bytes, 1024);
+            assert_eq!(timing.part_2_stats.memory, None);
         }
 
         #[test]
@@ -247,9 +421,21 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_part_stats() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[2].part_1_stats, timings.data[2].part_1_stats);
+            assert_eq!(parsed.data[2].parse, Some("12µs".to_string()));
+            assert_eq!(parsed.data[0].part_1_stats, Default::default());
+            assert_eq!(parsed.data[0].parse, None);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -271,7 +457,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartStats, Timing, Timings},
         };
 
         #[test]
@@ -282,10 +468,13 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -296,10 +485,13 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,17 +502,20 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartStats, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -334,6 +529,9 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +552,9 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    part_1_stats: PartStats::default(),
+                    part_2_stats: PartStats::default(),
+                    parse: None,
                 }],
             };
             let merged = timings.merge(&other);